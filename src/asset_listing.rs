@@ -0,0 +1,10 @@
+use super::*;
+
+/// Whether an asset is vetted for trading or sanctioned, as decided by `Config::AdminOrigin`.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum AssetListing {
+    /// The asset may be traded, required for every asset while the allowlist is enforced.
+    Allowed,
+    /// The asset may not be traded, whether or not the allowlist is enforced.
+    Denied,
+}
@@ -0,0 +1,1092 @@
+//! Benchmarking setup for the DEX pallet
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use crate::asset_listing::AssetListing;
+use crate::liquidity_pool::{
+    AssetPair, PoolAssets, PoolKind, PoolStatus, PriceObservation, MAX_AMPLIFICATION_CHANGE,
+};
+use crate::native_or_asset::NativeOrAssets;
+use crate::Pallet as Dex;
+use frame_benchmarking::v2::*;
+use frame_support::traits::fungibles::{Create, Inspect, Mutate};
+use frame_support::traits::tokens::Preservation;
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, SaturatedConversion, Saturating};
+use sp_std::vec::Vec;
+
+// An amount comfortably above the minimum liquidity, used as the unit of all deposits
+fn unit<T: Config>() -> AssetBalanceOf<T> {
+    T::MinimumLiquidity::get().max(1_000u32.into())
+}
+
+// Give an account enough of the native currency to exist and to create a pool for every hop of
+// the longest path
+fn fund_native<T: Config>(who: &AccountIdOf<T>) {
+    let amount = <T::NativeBalance as fungible::Inspect<_>>::minimum_balance()
+        .saturating_mul(1_000u32.into())
+        .saturating_add(
+            T::PoolCreationDeposit::get().saturating_mul(T::MaxSwapPathLength::get().into()),
+        );
+    <T::NativeBalance as fungible::Mutate<_>>::set_balance(who, amount);
+}
+
+// Create a new asset and mint a large balance of it to `holder`, allowing it under an enforced
+// allowlist. Ids are taken from the top of the id space to stay clear of the liquidity tokens.
+fn create_asset<T: Config>(holder: &AccountIdOf<T>) -> AssetKindOf<T> {
+    let mut id: AssetIdOf<T> = u32::MAX;
+    while T::Fungibles::asset_exists(id) {
+        id -= 1;
+    }
+    T::Fungibles::create(id, holder.clone(), true, One::one()).unwrap();
+    T::Fungibles::mint_into(id, holder, unit::<T>().saturating_mul(1_000_000u32.into())).unwrap();
+    let asset = NativeOrAssetId::Asset(id);
+    AssetListings::<T>::insert(asset, AssetListing::Allowed);
+    AllowlistEnforced::<T>::put(true);
+    asset
+}
+
+// Returns an origin allowed to create pools, with its account funded for the deposits
+fn pool_create_origin<T: Config>() -> T::RuntimeOrigin {
+    let origin = T::PoolCreateOrigin::try_successful_origin().unwrap();
+    let depositor = T::PoolCreateOrigin::ensure_origin(origin.clone()).unwrap();
+    fund_native::<T>(&depositor);
+    origin
+}
+
+// Create a pool for `asset_a` and `asset_b` at the highest swap fee, with the protocol fee
+// switched on
+fn create_pool<T: Config>(asset_a: AssetKindOf<T>, asset_b: AssetKindOf<T>) {
+    Dex::<T>::create_liquidity_pool(
+        pool_create_origin::<T>(),
+        asset_a,
+        asset_b,
+        T::MaxSwapFee::get(),
+    )
+    .unwrap();
+    LiquidityPools::<T>::mutate(AssetPair::new(asset_a, asset_b), |maybe_pool| {
+        if let Some(liquidity_pool) = maybe_pool {
+            liquidity_pool.protocol_fee = T::MaxProtocolFee::get();
+        }
+    });
+}
+
+// Deposit liquidity into the pool of `asset_a` and `asset_b`
+fn add_liquidity<T: Config>(
+    caller: &AccountIdOf<T>,
+    asset_a: AssetKindOf<T>,
+    asset_b: AssetKindOf<T>,
+) {
+    let amount = unit::<T>().saturating_mul(1_000u32.into());
+    Dex::<T>::mint_liquidity(
+        RawOrigin::Signed(caller.clone()).into(),
+        asset_a,
+        asset_b,
+        amount,
+        amount,
+        Zero::zero(),
+        Zero::zero(),
+        None,
+    )
+    .unwrap();
+}
+
+// Fill the observations of the pool of `asset_a` and `asset_b` and move to the next block, so
+// that the next touch of the pool accumulates its prices and overwrites an observation
+fn fill_observations<T: Config>(asset_a: AssetKindOf<T>, asset_b: AssetKindOf<T>) {
+    let trading_pair = AssetPair::new(asset_a, asset_b);
+    let liquidity_pool = LiquidityPools::<T>::get(&trading_pair).unwrap();
+    PriceObservations::<T>::mutate(&trading_pair, |observations| {
+        for _ in 0..T::MaxPriceObservations::get() {
+            observations.push(PriceObservation {
+                block_number: liquidity_pool.last_update,
+                price_cumulative: liquidity_pool.price_cumulative,
+            });
+        }
+    });
+    frame_system::Pallet::<T>::set_block_number(liquidity_pool.last_update + One::one());
+}
+
+// Create a funded caller with a pool of two new assets holding liquidity
+fn setup_pool<T: Config>() -> (AccountIdOf<T>, AssetKindOf<T>, AssetKindOf<T>) {
+    let caller: AccountIdOf<T> = whitelisted_caller();
+    fund_native::<T>(&caller);
+    let asset_a = create_asset::<T>(&caller);
+    let asset_b = create_asset::<T>(&caller);
+    create_pool::<T>(asset_a, asset_b);
+    add_liquidity::<T>(&caller, asset_a, asset_b);
+    fill_observations::<T>(asset_a, asset_b);
+    (caller, asset_a, asset_b)
+}
+
+// The amplification coefficient of the StableSwap pools created for benchmarks
+fn stable_amplification<T: Config>() -> u32 {
+    T::MaxAmplification::get().min(100)
+}
+
+// The furthest a ramp can move the amplification coefficient of a new StableSwap pool
+fn ramp_target<T: Config>() -> u32 {
+    T::MaxAmplification::get().min(stable_amplification::<T>() * MAX_AMPLIFICATION_CHANGE)
+}
+
+// Create a funded caller with a StableSwap pool of two new assets holding liquidity
+fn setup_stable_pool<T: Config>() -> (AccountIdOf<T>, AssetKindOf<T>, AssetKindOf<T>) {
+    let caller: AccountIdOf<T> = whitelisted_caller();
+    fund_native::<T>(&caller);
+    let asset_a = create_asset::<T>(&caller);
+    let asset_b = create_asset::<T>(&caller);
+    Dex::<T>::create_stable_pool(
+        pool_create_origin::<T>(),
+        asset_a,
+        asset_b,
+        T::MaxSwapFee::get(),
+        stable_amplification::<T>(),
+    )
+    .unwrap();
+    add_liquidity::<T>(&caller, asset_a, asset_b);
+    (caller, asset_a, asset_b)
+}
+
+// Create a funded caller with a path of `length` new assets and a pool holding liquidity for
+// every hop
+fn setup_path<T: Config>(length: u32) -> (AccountIdOf<T>, Vec<AssetKindOf<T>>) {
+    let caller: AccountIdOf<T> = whitelisted_caller();
+    fund_native::<T>(&caller);
+    let path: Vec<_> = (0..length).map(|_| create_asset::<T>(&caller)).collect();
+    for hop in path.windows(2) {
+        create_pool::<T>(hop[0], hop[1]);
+        add_liquidity::<T>(&caller, hop[0], hop[1]);
+        fill_observations::<T>(hop[0], hop[1]);
+    }
+    (caller, path)
+}
+
+// Create a funded caller with `count` new assets, sorted like the key of their multi-asset pool
+fn setup_pool_assets<T: Config>(count: u32) -> (AccountIdOf<T>, PoolAssets<T>) {
+    let caller: AccountIdOf<T> = whitelisted_caller();
+    fund_native::<T>(&caller);
+    let mut assets: Vec<_> = (0..count).map(|_| create_asset::<T>(&caller)).collect();
+    assets.sort();
+    (caller, PoolAssets::<T>::try_from(assets).unwrap())
+}
+
+// Create a multi-asset pool of `assets` at the highest swap fee, with the protocol fee switched
+// on
+fn create_multi_asset_pool<T: Config>(assets: &PoolAssets<T>) {
+    Dex::<T>::create_multi_asset_pool(
+        pool_create_origin::<T>(),
+        assets.clone(),
+        T::MaxSwapFee::get(),
+    )
+    .unwrap();
+    MultiAssetPools::<T>::mutate(assets, |maybe_pool| {
+        if let Some(multi_asset_pool) = maybe_pool {
+            multi_asset_pool.protocol_fee = T::MaxProtocolFee::get();
+        }
+    });
+}
+
+// The same amount of each asset of a multi-asset pool
+fn multi_asset_amounts<T: Config>(
+    assets: &PoolAssets<T>,
+    amount: AssetBalanceOf<T>,
+) -> BoundedVec<(AssetKindOf<T>, AssetBalanceOf<T>), T::MaxAssetsPerPool> {
+    let amounts: Vec<_> = assets.iter().map(|asset| (*asset, amount)).collect();
+    BoundedVec::try_from(amounts).unwrap()
+}
+
+// Deposit liquidity into the multi-asset pool of `assets`
+fn add_multi_asset_liquidity<T: Config>(caller: &AccountIdOf<T>, assets: &PoolAssets<T>) {
+    let amount = unit::<T>().saturating_mul(1_000u32.into());
+    Dex::<T>::mint_multi_asset_liquidity(
+        RawOrigin::Signed(caller.clone()).into(),
+        multi_asset_amounts::<T>(assets, amount),
+        Zero::zero(),
+        None,
+    )
+    .unwrap();
+}
+
+// The tick spacing of the concentrated liquidity pools created for benchmarks
+const TICK_SPACING: u32 = 10;
+
+// The ticks bounding the positions opened for benchmarks, around the initial price
+const POSITION_TICKS: (i32, i32) = (-1_000, 1_000);
+
+// The liquidity of the positions opened for benchmarks
+fn position_liquidity<T: Config>() -> u128 {
+    unit::<T>().saturating_mul(1_000u32.into()).saturated_into()
+}
+
+// Create a funded caller with a concentrated liquidity pool of two new assets at tick zero
+fn setup_concentrated_pool<T: Config>() -> (AccountIdOf<T>, AssetKindOf<T>, AssetKindOf<T>) {
+    let caller: AccountIdOf<T> = whitelisted_caller();
+    fund_native::<T>(&caller);
+    let asset_a = create_asset::<T>(&caller);
+    let asset_b = create_asset::<T>(&caller);
+    Dex::<T>::create_concentrated_pool(
+        pool_create_origin::<T>(),
+        asset_a,
+        asset_b,
+        T::MaxSwapFee::get(),
+        TICK_SPACING,
+        0,
+    )
+    .unwrap();
+    (caller, asset_a, asset_b)
+}
+
+// Open a position of the caller in the concentrated liquidity pool of `asset_a` and `asset_b`
+fn open_position<T: Config>(
+    caller: &AccountIdOf<T>,
+    asset_a: AssetKindOf<T>,
+    asset_b: AssetKindOf<T>,
+) {
+    Dex::<T>::open_position(
+        RawOrigin::Signed(caller.clone()).into(),
+        asset_a,
+        asset_b,
+        POSITION_TICKS.0,
+        POSITION_TICKS.1,
+        position_liquidity::<T>(),
+        AssetBalanceOf::<T>::max_value(),
+        AssetBalanceOf::<T>::max_value(),
+        None,
+    )
+    .unwrap();
+}
+
+// Initialize ticks of the concentrated liquidity pool of `asset_a` and `asset_b`: a position from
+// the lowest tick to above the price, and `narrow_positions` positions below the price that a
+// large enough swap selling asset A crosses one after another
+fn fill_ticks<T: Config>(
+    caller: &AccountIdOf<T>,
+    asset_a: AssetKindOf<T>,
+    asset_b: AssetKindOf<T>,
+    narrow_positions: i32,
+) {
+    let spacing = TICK_SPACING as i32;
+    let lowest_tick = concentrated_liquidity::MIN_TICK / spacing * spacing;
+    let narrow_ranges =
+        (0..narrow_positions).map(|k| (-2 * spacing * (k + 1), -2 * spacing * k - spacing));
+    for (tick_lower, tick_upper) in [(lowest_tick, POSITION_TICKS.1)]
+        .into_iter()
+        .chain(narrow_ranges)
+    {
+        Dex::<T>::open_position(
+            RawOrigin::Signed(caller.clone()).into(),
+            asset_a,
+            asset_b,
+            tick_lower,
+            tick_upper,
+            position_liquidity::<T>(),
+            AssetBalanceOf::<T>::max_value(),
+            AssetBalanceOf::<T>::max_value(),
+            None,
+        )
+        .unwrap();
+    }
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn create_liquidity_pool() {
+        let caller: AccountIdOf<T> = whitelisted_caller();
+        let asset_a = create_asset::<T>(&caller);
+        let asset_b = create_asset::<T>(&caller);
+        let origin = pool_create_origin::<T>();
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            asset_a,
+            asset_b,
+            T::MaxSwapFee::get(),
+        );
+
+        assert!(LiquidityPools::<T>::contains_key(AssetPair::new(
+            asset_a, asset_b
+        )));
+    }
+
+    // The first deposit also locks the minimum liquidity in the pool account
+    #[benchmark]
+    fn mint_liquidity() {
+        let caller: AccountIdOf<T> = whitelisted_caller();
+        fund_native::<T>(&caller);
+        let asset_a = create_asset::<T>(&caller);
+        let asset_b = create_asset::<T>(&caller);
+        create_pool::<T>(asset_a, asset_b);
+        fill_observations::<T>(asset_a, asset_b);
+        let amount = unit::<T>().saturating_mul(1_000u32.into());
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            asset_a,
+            asset_b,
+            amount,
+            amount,
+            Zero::zero(),
+            Zero::zero(),
+            None,
+        );
+
+        let liquidity_pool = LiquidityPools::<T>::get(AssetPair::new(asset_a, asset_b)).unwrap();
+        assert!(!liquidity_pool.total_liquidity.is_zero());
+    }
+
+    // Paying out to a new beneficiary creates its asset accounts
+    #[benchmark]
+    fn burn_liquidity() {
+        let (caller, asset_a, asset_b) = setup_pool::<T>();
+        let beneficiary: AccountIdOf<T> = account("beneficiary", 0, 0);
+        let liquidity = unit::<T>();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            asset_a,
+            asset_b,
+            liquidity,
+            Zero::zero(),
+            Zero::zero(),
+            Some(beneficiary.clone()),
+            None,
+        );
+
+        assert!(!NativeOrAssets::<T>::balance(asset_a, &beneficiary)
+            .unwrap()
+            .is_zero());
+    }
+
+    #[benchmark]
+    fn swap() {
+        let (caller, asset_a, asset_b) = setup_pool::<T>();
+        let amount_in = unit::<T>();
+        let balance_before = NativeOrAssets::<T>::balance(asset_b, &caller).unwrap();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            asset_a,
+            asset_b,
+            amount_in,
+            Zero::zero(),
+            None,
+        );
+
+        assert!(NativeOrAssets::<T>::balance(asset_b, &caller).unwrap() > balance_before);
+    }
+
+    #[benchmark]
+    fn swap_exact_out() {
+        let (caller, asset_a, asset_b) = setup_pool::<T>();
+        let amount_out = unit::<T>();
+        let balance_before = NativeOrAssets::<T>::balance(asset_b, &caller).unwrap();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            asset_a,
+            asset_b,
+            amount_out,
+            AssetBalanceOf::<T>::max_value(),
+            None,
+        );
+
+        assert_eq!(
+            NativeOrAssets::<T>::balance(asset_b, &caller).unwrap(),
+            balance_before.saturating_add(amount_out)
+        );
+    }
+
+    #[benchmark]
+    fn swap_exact_in_path(n: Linear<2, { T::MaxSwapPathLength::get() }>) {
+        let (caller, path) = setup_path::<T>(n);
+        let asset_out = *path.last().unwrap();
+        let balance_before = NativeOrAssets::<T>::balance(asset_out, &caller).unwrap();
+        let path = BoundedVec::try_from(path).unwrap();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            path,
+            unit::<T>(),
+            Zero::zero(),
+            None,
+        );
+
+        assert!(NativeOrAssets::<T>::balance(asset_out, &caller).unwrap() > balance_before);
+    }
+
+    #[benchmark]
+    fn swap_exact_out_path(n: Linear<2, { T::MaxSwapPathLength::get() }>) {
+        let (caller, path) = setup_path::<T>(n);
+        let asset_out = *path.last().unwrap();
+        let balance_before = NativeOrAssets::<T>::balance(asset_out, &caller).unwrap();
+        let path = BoundedVec::try_from(path).unwrap();
+        let amount_out = unit::<T>();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            path,
+            amount_out,
+            AssetBalanceOf::<T>::max_value(),
+            None,
+        );
+
+        assert_eq!(
+            NativeOrAssets::<T>::balance(asset_out, &caller).unwrap(),
+            balance_before.saturating_add(amount_out)
+        );
+    }
+
+    #[benchmark]
+    fn set_swap_fee() -> Result<(), BenchmarkError> {
+        let (_, asset_a, asset_b) = setup_pool::<T>();
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let swap_fee = T::MinSwapFee::get();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, asset_a, asset_b, swap_fee);
+
+        let liquidity_pool = LiquidityPools::<T>::get(AssetPair::new(asset_a, asset_b)).unwrap();
+        assert_eq!(liquidity_pool.swap_fee, swap_fee);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_protocol_fee() -> Result<(), BenchmarkError> {
+        let (_, asset_a, asset_b) = setup_pool::<T>();
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let protocol_fee = T::MaxProtocolFee::get();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, asset_a, asset_b, protocol_fee);
+
+        let liquidity_pool = LiquidityPools::<T>::get(AssetPair::new(asset_a, asset_b)).unwrap();
+        assert_eq!(liquidity_pool.protocol_fee, protocol_fee);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn sync() {
+        let (caller, asset_a, asset_b) = setup_pool::<T>();
+        let pool_account = Dex::<T>::pool_account_id(&AssetPair::new(asset_a, asset_b));
+        for asset in [asset_a, asset_b] {
+            NativeOrAssets::<T>::transfer(
+                asset,
+                &caller,
+                &pool_account,
+                unit::<T>(),
+                Preservation::Expendable,
+            )
+            .unwrap();
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), asset_a, asset_b);
+
+        let drift = Dex::<T>::reserve_drift(asset_a, asset_b).unwrap();
+        assert_eq!(drift.excess, (Zero::zero(), Zero::zero()));
+    }
+
+    // Paying out the excess of both assets to a new account creates its asset accounts
+    #[benchmark]
+    fn skim() {
+        let (caller, asset_a, asset_b) = setup_pool::<T>();
+        let pool_account = Dex::<T>::pool_account_id(&AssetPair::new(asset_a, asset_b));
+        for asset in [asset_a, asset_b] {
+            NativeOrAssets::<T>::transfer(
+                asset,
+                &caller,
+                &pool_account,
+                unit::<T>(),
+                Preservation::Expendable,
+            )
+            .unwrap();
+        }
+        let to: AccountIdOf<T> = account("to", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), asset_a, asset_b, to.clone());
+
+        assert_eq!(
+            NativeOrAssets::<T>::balance(asset_a, &to).unwrap(),
+            unit::<T>()
+        );
+    }
+
+    // Once all liquidity is burned the pool account still holds the locked minimum liquidity
+    // and the reserves backing it, which are swept to the protocol fee receiver
+    #[benchmark]
+    fn destroy_pool() {
+        let (caller, asset_a, asset_b) = setup_pool::<T>();
+        let trading_pair = AssetPair::new(asset_a, asset_b);
+        let liquidity_token = LiquidityPools::<T>::get(&trading_pair)
+            .unwrap()
+            .liquidity_token;
+        let liquidity = T::Fungibles::balance(liquidity_token, &caller);
+        Dex::<T>::burn_liquidity(
+            RawOrigin::Signed(caller.clone()).into(),
+            asset_a,
+            asset_b,
+            liquidity,
+            Zero::zero(),
+            Zero::zero(),
+            None,
+            None,
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), asset_a, asset_b);
+
+        assert!(!LiquidityPools::<T>::contains_key(&trading_pair));
+    }
+
+    #[benchmark]
+    fn set_asset_listing() -> Result<(), BenchmarkError> {
+        let asset = create_asset::<T>(&whitelisted_caller());
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            asset,
+            Some(AssetListing::Denied),
+        );
+
+        assert_eq!(AssetListings::<T>::get(asset), Some(AssetListing::Denied));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_allowlist_enforced() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, true);
+
+        assert!(AllowlistEnforced::<T>::get());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_global_status() -> Result<(), BenchmarkError> {
+        let origin =
+            T::PauseOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, PoolStatus::FullyPaused);
+
+        assert_eq!(GlobalStatus::<T>::get(), PoolStatus::FullyPaused);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_pool_status() -> Result<(), BenchmarkError> {
+        let (_, asset_a, asset_b) = setup_pool::<T>();
+        let origin =
+            T::PauseOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            asset_a,
+            asset_b,
+            PoolStatus::FullyPaused,
+        );
+
+        let liquidity_pool = LiquidityPools::<T>::get(AssetPair::new(asset_a, asset_b)).unwrap();
+        assert_eq!(liquidity_pool.status, PoolStatus::FullyPaused);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn create_stable_pool() {
+        let caller: AccountIdOf<T> = whitelisted_caller();
+        let asset_a = create_asset::<T>(&caller);
+        let asset_b = create_asset::<T>(&caller);
+        let origin = pool_create_origin::<T>();
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            asset_a,
+            asset_b,
+            T::MaxSwapFee::get(),
+            T::MaxAmplification::get(),
+        );
+
+        let liquidity_pool = LiquidityPools::<T>::get(AssetPair::new(asset_a, asset_b)).unwrap();
+        assert!(matches!(liquidity_pool.kind, PoolKind::StableSwap(_)));
+    }
+
+    #[benchmark]
+    fn ramp_amplification() -> Result<(), BenchmarkError> {
+        let (_, asset_a, asset_b) = setup_stable_pool::<T>();
+        let future_amplification = ramp_target::<T>();
+        let future_block = frame_system::Pallet::<T>::block_number()
+            .saturating_add(T::MinAmplificationRampDuration::get());
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            asset_a,
+            asset_b,
+            future_amplification,
+            future_block,
+        );
+
+        let liquidity_pool = LiquidityPools::<T>::get(AssetPair::new(asset_a, asset_b)).unwrap();
+        let PoolKind::StableSwap(amplification) = liquidity_pool.kind else {
+            panic!("the pool is a StableSwap pool");
+        };
+        assert_eq!(amplification.future, future_amplification);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn stop_amplification_ramp() -> Result<(), BenchmarkError> {
+        let (_, asset_a, asset_b) = setup_stable_pool::<T>();
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let future_block = frame_system::Pallet::<T>::block_number()
+            .saturating_add(T::MinAmplificationRampDuration::get());
+        Dex::<T>::ramp_amplification(
+            origin.clone(),
+            asset_a,
+            asset_b,
+            ramp_target::<T>(),
+            future_block,
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, asset_a, asset_b);
+
+        let liquidity_pool = LiquidityPools::<T>::get(AssetPair::new(asset_a, asset_b)).unwrap();
+        let PoolKind::StableSwap(amplification) = liquidity_pool.kind else {
+            panic!("the pool is a StableSwap pool");
+        };
+        assert_eq!(amplification.initial, amplification.future);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn create_weighted_pool() {
+        let caller: AccountIdOf<T> = whitelisted_caller();
+        let asset_a = create_asset::<T>(&caller);
+        let asset_b = create_asset::<T>(&caller);
+        let origin = pool_create_origin::<T>();
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            asset_a,
+            asset_b,
+            T::MaxSwapFee::get(),
+            Permill::from_percent(80),
+            Permill::from_percent(20),
+        );
+
+        let liquidity_pool = LiquidityPools::<T>::get(AssetPair::new(asset_a, asset_b)).unwrap();
+        assert!(matches!(liquidity_pool.kind, PoolKind::Weighted { .. }));
+    }
+
+    #[benchmark]
+    fn create_multi_asset_pool(n: Linear<2, { T::MaxAssetsPerPool::get() }>) {
+        let (_, assets) = setup_pool_assets::<T>(n);
+        let origin = pool_create_origin::<T>();
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            assets.clone(),
+            T::MaxSwapFee::get(),
+        );
+
+        assert!(MultiAssetPools::<T>::contains_key(&assets));
+    }
+
+    // The first deposit also locks the minimum liquidity in the pool account
+    #[benchmark]
+    fn mint_multi_asset_liquidity(n: Linear<2, { T::MaxAssetsPerPool::get() }>) {
+        let (caller, assets) = setup_pool_assets::<T>(n);
+        create_multi_asset_pool::<T>(&assets);
+        let amount = unit::<T>().saturating_mul(1_000u32.into());
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            multi_asset_amounts::<T>(&assets, amount),
+            Zero::zero(),
+            None,
+        );
+
+        let multi_asset_pool = MultiAssetPools::<T>::get(&assets).unwrap();
+        assert!(!multi_asset_pool.total_liquidity.is_zero());
+    }
+
+    #[benchmark]
+    fn burn_multi_asset_liquidity(n: Linear<2, { T::MaxAssetsPerPool::get() }>) {
+        let (caller, assets) = setup_pool_assets::<T>(n);
+        create_multi_asset_pool::<T>(&assets);
+        add_multi_asset_liquidity::<T>(&caller, &assets);
+        let liquidity = unit::<T>();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            liquidity,
+            multi_asset_amounts::<T>(&assets, Zero::zero()),
+            None,
+        );
+
+        let multi_asset_pool = MultiAssetPools::<T>::get(&assets).unwrap();
+        assert!(multi_asset_pool.total_liquidity < unit::<T>().saturating_mul(1_000u32.into()));
+    }
+
+    #[benchmark]
+    fn swap_multi_asset() {
+        let (caller, assets) = setup_pool_assets::<T>(T::MaxAssetsPerPool::get());
+        create_multi_asset_pool::<T>(&assets);
+        add_multi_asset_liquidity::<T>(&caller, &assets);
+        let (asset_in, asset_out) = (assets[0], assets[assets.len() - 1]);
+        let amount_in = unit::<T>();
+        let balance_before = NativeOrAssets::<T>::balance(asset_out, &caller).unwrap();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            assets.clone(),
+            asset_in,
+            asset_out,
+            amount_in,
+            Zero::zero(),
+            None,
+        );
+
+        assert!(NativeOrAssets::<T>::balance(asset_out, &caller).unwrap() > balance_before);
+    }
+
+    #[benchmark]
+    fn create_concentrated_pool() {
+        let caller: AccountIdOf<T> = whitelisted_caller();
+        let asset_a = create_asset::<T>(&caller);
+        let asset_b = create_asset::<T>(&caller);
+        let origin = pool_create_origin::<T>();
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            asset_a,
+            asset_b,
+            T::MaxSwapFee::get(),
+            TICK_SPACING,
+            0,
+        );
+
+        let liquidity_pool = LiquidityPools::<T>::get(AssetPair::new(asset_a, asset_b)).unwrap();
+        assert!(matches!(liquidity_pool.kind, PoolKind::Concentrated(_)));
+    }
+
+    #[benchmark]
+    fn open_position() {
+        let (caller, asset_a, asset_b) = setup_concentrated_pool::<T>();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            asset_a,
+            asset_b,
+            POSITION_TICKS.0,
+            POSITION_TICKS.1,
+            position_liquidity::<T>(),
+            AssetBalanceOf::<T>::max_value(),
+            AssetBalanceOf::<T>::max_value(),
+            None,
+        );
+
+        assert!(Positions::<T>::contains_key(
+            AssetPair::new(asset_a, asset_b),
+            (caller, POSITION_TICKS.0, POSITION_TICKS.1)
+        ));
+    }
+
+    #[benchmark]
+    fn increase_position() {
+        let (caller, asset_a, asset_b) = setup_concentrated_pool::<T>();
+        open_position::<T>(&caller, asset_a, asset_b);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            asset_a,
+            asset_b,
+            POSITION_TICKS.0,
+            POSITION_TICKS.1,
+            position_liquidity::<T>(),
+            AssetBalanceOf::<T>::max_value(),
+            AssetBalanceOf::<T>::max_value(),
+            None,
+        );
+
+        let position = Positions::<T>::get(
+            AssetPair::new(asset_a, asset_b),
+            (caller, POSITION_TICKS.0, POSITION_TICKS.1),
+        )
+        .unwrap();
+        assert_eq!(position.liquidity, position_liquidity::<T>() * 2);
+    }
+
+    #[benchmark]
+    fn decrease_position() {
+        let (caller, asset_a, asset_b) = setup_concentrated_pool::<T>();
+        open_position::<T>(&caller, asset_a, asset_b);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            asset_a,
+            asset_b,
+            POSITION_TICKS.0,
+            POSITION_TICKS.1,
+            position_liquidity::<T>() / 2,
+            Zero::zero(),
+            Zero::zero(),
+            None,
+        );
+
+        let position = Positions::<T>::get(
+            AssetPair::new(asset_a, asset_b),
+            (caller, POSITION_TICKS.0, POSITION_TICKS.1),
+        )
+        .unwrap();
+        assert!(!position.tokens_owed.0.is_zero());
+    }
+
+    // The position has earned fees and has amounts removed from it to collect
+    #[benchmark]
+    fn collect_position() {
+        let (caller, asset_a, asset_b) = setup_concentrated_pool::<T>();
+        open_position::<T>(&caller, asset_a, asset_b);
+        Dex::<T>::swap(
+            RawOrigin::Signed(caller.clone()).into(),
+            asset_a,
+            asset_b,
+            unit::<T>(),
+            Zero::zero(),
+            None,
+        )
+        .unwrap();
+        Dex::<T>::decrease_position(
+            RawOrigin::Signed(caller.clone()).into(),
+            asset_a,
+            asset_b,
+            POSITION_TICKS.0,
+            POSITION_TICKS.1,
+            position_liquidity::<T>() / 2,
+            Zero::zero(),
+            Zero::zero(),
+            None,
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            asset_a,
+            asset_b,
+            POSITION_TICKS.0,
+            POSITION_TICKS.1,
+            None,
+        );
+
+        let position = Positions::<T>::get(
+            AssetPair::new(asset_a, asset_b),
+            (caller, POSITION_TICKS.0, POSITION_TICKS.1),
+        )
+        .unwrap();
+        assert!(position.tokens_owed.0.is_zero() && position.tokens_owed.1.is_zero());
+    }
+
+    #[benchmark]
+    fn create_curve_pool() {
+        let caller: AccountIdOf<T> = whitelisted_caller();
+        let asset_a = create_asset::<T>(&caller);
+        let asset_b = create_asset::<T>(&caller);
+        let origin = pool_create_origin::<T>();
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            asset_a,
+            asset_b,
+            T::MaxSwapFee::get(),
+            T::Curve::default(),
+        );
+
+        let liquidity_pool = LiquidityPools::<T>::get(AssetPair::new(asset_a, asset_b)).unwrap();
+        assert!(matches!(liquidity_pool.kind, PoolKind::Curve(_)));
+    }
+
+    #[benchmark]
+    fn claim_protocol_fees() {
+        let (caller, asset_a, asset_b) = setup_pool::<T>();
+        for (asset_in, asset_out) in [(asset_a, asset_b), (asset_b, asset_a)] {
+            Dex::<T>::swap(
+                RawOrigin::Signed(caller.clone()).into(),
+                asset_in,
+                asset_out,
+                unit::<T>(),
+                Zero::zero(),
+                None,
+            )
+            .unwrap();
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), asset_a, asset_b);
+
+        let liquidity_pool = LiquidityPools::<T>::get(AssetPair::new(asset_a, asset_b)).unwrap();
+        assert!(
+            liquidity_pool.protocol_fees.0.is_zero() && liquidity_pool.protocol_fees.1.is_zero()
+        );
+    }
+
+    #[benchmark]
+    fn set_multi_asset_pool_status() -> Result<(), BenchmarkError> {
+        let (_, assets) = setup_pool_assets::<T>(T::MaxAssetsPerPool::get());
+        create_multi_asset_pool::<T>(&assets);
+        let origin =
+            T::PauseOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            assets.clone(),
+            PoolStatus::FullyPaused,
+        );
+
+        let multi_asset_pool = MultiAssetPools::<T>::get(&assets).unwrap();
+        assert_eq!(multi_asset_pool.status, PoolStatus::FullyPaused);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_multi_asset_protocol_fee() -> Result<(), BenchmarkError> {
+        let (_, assets) = setup_pool_assets::<T>(T::MaxAssetsPerPool::get());
+        create_multi_asset_pool::<T>(&assets);
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let protocol_fee = T::MaxProtocolFee::get();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, assets.clone(), protocol_fee);
+
+        let multi_asset_pool = MultiAssetPools::<T>::get(&assets).unwrap();
+        assert_eq!(multi_asset_pool.protocol_fee, protocol_fee);
+        Ok(())
+    }
+
+    // Every asset of the pool has protocol fees to pay out
+    #[benchmark]
+    fn claim_multi_asset_protocol_fees(n: Linear<2, { T::MaxAssetsPerPool::get() }>) {
+        let (caller, assets) = setup_pool_assets::<T>(n);
+        create_multi_asset_pool::<T>(&assets);
+        add_multi_asset_liquidity::<T>(&caller, &assets);
+        for index in 0..assets.len() {
+            let asset_out = assets[(index + 1) % assets.len()];
+            Dex::<T>::swap_multi_asset(
+                RawOrigin::Signed(caller.clone()).into(),
+                assets.clone(),
+                assets[index],
+                asset_out,
+                unit::<T>(),
+                Zero::zero(),
+                None,
+            )
+            .unwrap();
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), assets.clone());
+
+        let multi_asset_pool = MultiAssetPools::<T>::get(&assets).unwrap();
+        assert!(multi_asset_pool
+            .protocol_fees
+            .iter()
+            .all(|accrued| accrued.is_zero()));
+    }
+
+    // Only the locked minimum liquidity is left, so the pool account holds every asset
+    #[benchmark]
+    fn destroy_multi_asset_pool(n: Linear<2, { T::MaxAssetsPerPool::get() }>) {
+        let (caller, assets) = setup_pool_assets::<T>(n);
+        create_multi_asset_pool::<T>(&assets);
+        add_multi_asset_liquidity::<T>(&caller, &assets);
+        let liquidity_token = MultiAssetPools::<T>::get(&assets).unwrap().liquidity_token;
+        Dex::<T>::burn_multi_asset_liquidity(
+            RawOrigin::Signed(caller.clone()).into(),
+            T::Fungibles::balance(liquidity_token, &caller),
+            multi_asset_amounts::<T>(&assets, Zero::zero()),
+            None,
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), assets.clone());
+
+        assert!(!MultiAssetPools::<T>::contains_key(&assets));
+    }
+
+    // `swap` and `swap_exact_out` against a concentrated liquidity pool, crossing `n` initialized
+    // ticks. The two ticks of the position around the price are never crossed, and each narrow
+    // position has two ticks, so an odd `n` crosses one tick less.
+    #[benchmark]
+    fn swap_concentrated(n: Linear<0, { T::MaxInitializedTicks::get().saturating_sub(2) }>) {
+        let (caller, asset_a, asset_b) = setup_concentrated_pool::<T>();
+        let narrow_positions = (n / 2) as i32;
+        fill_ticks::<T>(&caller, asset_a, asset_b, narrow_positions);
+        let trading_pair = AssetPair::new(asset_a, asset_b);
+        assert_eq!(
+            Ticks::<T>::get(&trading_pair).len() as u32,
+            2 * narrow_positions as u32 + 2
+        );
+        let amount_in = unit::<T>().saturating_mul(1_000u32.into());
+
+        #[block]
+        {
+            Dex::<T>::swap(
+                RawOrigin::Signed(caller).into(),
+                asset_a,
+                asset_b,
+                amount_in,
+                Zero::zero(),
+                None,
+            )
+            .unwrap();
+        }
+
+        let state = LiquidityPools::<T>::get(&trading_pair)
+            .unwrap()
+            .concentrated()
+            .unwrap();
+        assert!(state.tick < -2 * TICK_SPACING as i32 * narrow_positions);
+    }
+
+    impl_benchmark_test_suite!(Dex, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,399 @@
+// Math of concentrated liquidity pools, in the style of Uniswap v3. The price of asset A in
+// asset B is 1.0001^tick at every tick, and pools track the square root of the price as a Q64.64
+// fixed point number, the integer part in the upper 64 bits. Liquidity `L` between two square
+// root prices `sa < sb` is backed by `L·(sb - sa)/(sa·sb)` of asset A and `L·(sb - sa)` of asset
+// B. Intermediate values are computed in 256 and 512 bits.
+
+use sp_core::{U256, U512};
+use sp_runtime::{FixedPointNumber, FixedU128};
+
+/// The lowest tick, at a square root price of about 2^-32
+pub const MIN_TICK: i32 = -443_636;
+
+/// The highest tick, at a square root price of about 2^32
+pub const MAX_TICK: i32 = 443_636;
+
+// The number of fractional bits of a square root price
+const RESOLUTION: usize = 64;
+
+// 2^128 / 1.0001^(2^i / 2) for each bit `i` of the absolute value of a tick, rounded to the
+// nearest integer
+const TICK_FACTORS: [u128; 19] = [
+    0xfffcb933_bd6fad37_aa2d162d_1a594001,
+    0xfff97272_373d4132_59a46990_580e213a,
+    0xfff2e50f_5f656932_ef12357c_f3c7fdcc,
+    0xffe5caca_7e10e4e6_1c3624ea_a0941cd0,
+    0xffcb9843_d60f6159_c9db5883_5c926644,
+    0xff973b41_fa98c081_472e6896_dfb254c0,
+    0xff2ea164_66c96a38_43ec78b3_26b52861,
+    0xfe5dee04_6a99a2a8_11c461f1_969c3053,
+    0xfcbe86c7_900a88ae_dcffc83b_479aa3a4,
+    0xf987a725_3ac41317_6f2b074c_f7815e54,
+    0xf3392b08_22b70005_940c7a39_8e4b70f3,
+    0xe7159475_a2c29b74_43b29c7f_a6e889d9,
+    0xd097f3bd_fd2022b8_845ad8f7_92aa5825,
+    0xa9f74646_2d870fdf_8a65dc1f_90e061e5,
+    0x70d869a1_56d2a1b8_90bb3df6_2baf32f7,
+    0x31be135f_97d08fd9_81231505_542fcfa6,
+    0x09aa508b_5b7a84e1_c677de54_f3e99bc9,
+    0x005d6af8_dedb8119_6699c329_225ee604,
+    0x00002216_e584f5fa_1ea92604_1bedfe98,
+];
+
+// Function to compute the square root price at a tick, `None` outside of the tick range
+pub fn sqrt_price_at_tick(tick: i32) -> Option<u128> {
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return None;
+    }
+
+    // 1.0001^(-|tick| / 2) as a Q128.128 number, multiplying the factors of the set bits
+    let absolute_tick = tick.unsigned_abs();
+    let mut ratio = U256::one() << 128;
+    for (bit, factor) in TICK_FACTORS.iter().enumerate() {
+        if absolute_tick & (1 << bit) != 0 {
+            ratio = (ratio * U256::from(*factor)) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+
+    // Round up to Q64.64 so that the tick of a square root price is consistent with this
+    let remainder = ratio & ((U256::one() << RESOLUTION) - 1);
+    let sqrt_price = (ratio >> RESOLUTION) + U256::from(!remainder.is_zero() as u8);
+    sqrt_price.try_into().ok()
+}
+
+// Function to find the highest tick whose square root price is at most `sqrt_price`, `None`
+// outside of the tick range
+pub fn tick_at_sqrt_price(sqrt_price: u128) -> Option<i32> {
+    if sqrt_price < sqrt_price_at_tick(MIN_TICK)? || sqrt_price > sqrt_price_at_tick(MAX_TICK)? {
+        return None;
+    }
+
+    // Binary search, the square root price increases with the tick
+    let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+    while low < high {
+        let middle = low + (high - low + 1) / 2;
+        if sqrt_price_at_tick(middle)? <= sqrt_price {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+    Some(low)
+}
+
+// Helper function to compute a·b/denominator without overflowing in between
+fn mul_div(a: U256, b: U256, denominator: U256, round_up: bool) -> Option<U256> {
+    if denominator.is_zero() {
+        return None;
+    }
+    let product = a.full_mul(b);
+    let denominator = U512::from(denominator);
+    let mut quotient = product / denominator;
+    if round_up && !(product % denominator).is_zero() {
+        quotient = quotient.checked_add(U512::one())?;
+    }
+    quotient.try_into().ok()
+}
+
+// Function to compute the amount of asset A backing `liquidity` between two square root prices
+pub fn amount_a_delta(
+    sqrt_price_a: u128,
+    sqrt_price_b: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Option<u128> {
+    let (lower, upper) = if sqrt_price_a <= sqrt_price_b {
+        (sqrt_price_a, sqrt_price_b)
+    } else {
+        (sqrt_price_b, sqrt_price_a)
+    };
+    if lower == 0 {
+        return None;
+    }
+
+    // L·2^64·(upper - lower) / upper / lower
+    let numerator = U256::from(liquidity) << RESOLUTION;
+    let amount = mul_div(
+        numerator,
+        U256::from(upper - lower),
+        U256::from(upper),
+        round_up,
+    )?;
+    let lower = U256::from(lower);
+    let mut quotient = amount / lower;
+    if round_up && !(amount % lower).is_zero() {
+        quotient += U256::one();
+    }
+    quotient.try_into().ok()
+}
+
+// Function to compute the amount of asset B backing `liquidity` between two square root prices
+pub fn amount_b_delta(
+    sqrt_price_a: u128,
+    sqrt_price_b: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Option<u128> {
+    let difference = sqrt_price_a.abs_diff(sqrt_price_b);
+    mul_div(
+        U256::from(liquidity),
+        U256::from(difference),
+        U256::one() << RESOLUTION,
+        round_up,
+    )?
+    .try_into()
+    .ok()
+}
+
+// Function to compute the square root price after adding `amount_in` of asset A, when
+// `a_to_b`, or of asset B to `liquidity`. The price moves down for asset A and up for asset B,
+// rounded so that the pool never gives out more than the amount in pays for.
+pub fn next_sqrt_price_from_input(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount_in: u128,
+    a_to_b: bool,
+) -> Option<u128> {
+    if liquidity == 0 {
+        return None;
+    }
+
+    if a_to_b {
+        // L·√P / (L + amount·√P), rounded up
+        let numerator = U256::from(liquidity) << RESOLUTION;
+        let denominator =
+            numerator.checked_add(U256::from(amount_in).checked_mul(U256::from(sqrt_price))?)?;
+        mul_div(numerator, U256::from(sqrt_price), denominator, true)?
+            .try_into()
+            .ok()
+    } else {
+        // √P + amount / L, rounded down
+        let increase = (U256::from(amount_in) << RESOLUTION) / U256::from(liquidity);
+        U256::from(sqrt_price)
+            .checked_add(increase)?
+            .try_into()
+            .ok()
+    }
+}
+
+// Function to compute the square root price after taking `amount_out` of asset B, when
+// `a_to_b`, or of asset A out of `liquidity`. The price moves down for asset B and up for asset
+// A, rounded so that the pool never gives out more than the amount in pays for.
+pub fn next_sqrt_price_from_output(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount_out: u128,
+    a_to_b: bool,
+) -> Option<u128> {
+    if liquidity == 0 {
+        return None;
+    }
+
+    if a_to_b {
+        // √P - amount / L, rounded down
+        let decrease = mul_div(
+            U256::from(amount_out),
+            U256::one() << RESOLUTION,
+            U256::from(liquidity),
+            true,
+        )?;
+        U256::from(sqrt_price)
+            .checked_sub(decrease)?
+            .try_into()
+            .ok()
+    } else {
+        // L·√P / (L - amount·√P), rounded up
+        let numerator = U256::from(liquidity) << RESOLUTION;
+        let denominator =
+            numerator.checked_sub(U256::from(amount_out).checked_mul(U256::from(sqrt_price))?)?;
+        mul_div(numerator, U256::from(sqrt_price), denominator, true)?
+            .try_into()
+            .ok()
+    }
+}
+
+/// The result of swapping within a range of constant liquidity.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SwapStep {
+    /// The square root price after the step
+    pub sqrt_price: u128,
+    /// The amount swapped in, without the fee
+    pub amount_in: u128,
+    /// The amount swapped out
+    pub amount_out: u128,
+    /// The swap fee paid on top of `amount_in`
+    pub fee: u128,
+}
+
+// Function to swap as much of `amount_remaining`, fee included, as fits before the square root
+// price reaches `sqrt_price_target`. The fee is given in parts per million.
+pub fn compute_swap_step(
+    sqrt_price: u128,
+    sqrt_price_target: u128,
+    liquidity: u128,
+    amount_remaining: u128,
+    fee_parts: u32,
+) -> Option<SwapStep> {
+    const PARTS: u128 = 1_000_000;
+    let fee_parts = fee_parts as u128;
+    let a_to_b = sqrt_price_target <= sqrt_price;
+
+    let amount_after_fee: u128 = mul_div(
+        U256::from(amount_remaining),
+        U256::from(PARTS.checked_sub(fee_parts)?),
+        U256::from(PARTS),
+        false,
+    )?
+    .try_into()
+    .ok()?;
+    let amount_to_target = if a_to_b {
+        amount_a_delta(sqrt_price_target, sqrt_price, liquidity, true)?
+    } else {
+        amount_b_delta(sqrt_price, sqrt_price_target, liquidity, true)?
+    };
+
+    let reaches_target = amount_after_fee >= amount_to_target;
+    let next_sqrt_price = if reaches_target {
+        sqrt_price_target
+    } else {
+        next_sqrt_price_from_input(sqrt_price, liquidity, amount_after_fee, a_to_b)?
+    };
+
+    let (amount_in, amount_out) = if a_to_b {
+        let amount_in = if reaches_target {
+            amount_to_target
+        } else {
+            amount_a_delta(next_sqrt_price, sqrt_price, liquidity, true)?
+        };
+        (
+            amount_in,
+            amount_b_delta(next_sqrt_price, sqrt_price, liquidity, false)?,
+        )
+    } else {
+        let amount_in = if reaches_target {
+            amount_to_target
+        } else {
+            amount_b_delta(sqrt_price, next_sqrt_price, liquidity, true)?
+        };
+        (
+            amount_in,
+            amount_a_delta(sqrt_price, next_sqrt_price, liquidity, false)?,
+        )
+    };
+
+    // A step that stops short of the target uses up the whole amount, the rest is the fee
+    let fee: u128 = if reaches_target {
+        mul_div(
+            U256::from(amount_in),
+            U256::from(fee_parts),
+            U256::from(PARTS - fee_parts),
+            true,
+        )?
+        .try_into()
+        .ok()?
+    } else {
+        amount_remaining.checked_sub(amount_in)?
+    };
+
+    Some(SwapStep {
+        sqrt_price: next_sqrt_price,
+        amount_in,
+        amount_out,
+        fee,
+    })
+}
+
+// Function to swap for as much of `amount_remaining` out as there is before the square root
+// price reaches `sqrt_price_target`. The amount in is rounded up and the fee on top of it, given
+// in parts per million, is charged like on an exact amount in. A step that stops short of the
+// target pays out the whole amount, so that every step either crosses a tick or ends the swap.
+pub fn compute_swap_step_exact_out(
+    sqrt_price: u128,
+    sqrt_price_target: u128,
+    liquidity: u128,
+    amount_remaining: u128,
+    fee_parts: u32,
+) -> Option<SwapStep> {
+    const PARTS: u128 = 1_000_000;
+    let fee_parts = fee_parts as u128;
+    let a_to_b = sqrt_price_target <= sqrt_price;
+
+    let amount_to_target = if a_to_b {
+        amount_b_delta(sqrt_price_target, sqrt_price, liquidity, false)?
+    } else {
+        amount_a_delta(sqrt_price, sqrt_price_target, liquidity, false)?
+    };
+
+    let reaches_target = amount_remaining >= amount_to_target;
+    let next_sqrt_price = if reaches_target {
+        sqrt_price_target
+    } else {
+        next_sqrt_price_from_output(sqrt_price, liquidity, amount_remaining, a_to_b)?
+    };
+
+    let amount_in = if a_to_b {
+        amount_a_delta(next_sqrt_price, sqrt_price, liquidity, true)?
+    } else {
+        amount_b_delta(sqrt_price, next_sqrt_price, liquidity, true)?
+    };
+    // The next price is rounded past the one paying out exactly the remaining amount, so the
+    // amount in always covers it
+    let amount_out = if reaches_target {
+        amount_to_target
+    } else {
+        amount_remaining
+    };
+
+    let fee: u128 = mul_div(
+        U256::from(amount_in),
+        U256::from(fee_parts),
+        U256::from(PARTS.checked_sub(fee_parts)?),
+        true,
+    )?
+    .try_into()
+    .ok()?;
+
+    Some(SwapStep {
+        sqrt_price: next_sqrt_price,
+        amount_in,
+        amount_out,
+        fee,
+    })
+}
+
+// Function to compute the price of asset A in asset B and of asset B in asset A as the inner
+// values of `FixedU128`, saturating at its bounds
+pub fn prices(sqrt_price: u128) -> (u128, u128) {
+    let one = U256::from(FixedU128::DIV);
+    let squared = U256::from(sqrt_price) * U256::from(sqrt_price);
+    let price_a = (squared * one) >> (2 * RESOLUTION);
+    let price_b = if squared.is_zero() {
+        U256::MAX
+    } else {
+        mul_div(U256::one() << (2 * RESOLUTION), one, squared, false).unwrap_or(U256::MAX)
+    };
+    (
+        price_a.try_into().unwrap_or(u128::MAX),
+        price_b.try_into().unwrap_or(u128::MAX),
+    )
+}
+
+// Function to add the fee earned per unit of liquidity, as a wrapping Q64.64 number, to a fee
+// growth accumulator
+pub fn accumulate_fee_growth(fee_growth: u128, fee: u128, liquidity: u128) -> u128 {
+    if liquidity == 0 {
+        return fee_growth;
+    }
+    let growth = (U256::from(fee) << RESOLUTION) / U256::from(liquidity);
+    fee_growth.wrapping_add(growth.low_u128())
+}
+
+// Function to compute the fees earned by `liquidity` since the fee growth inside its range was
+// `fee_growth_last`, truncated like the accumulators wrap
+pub fn fees_earned(fee_growth_inside: u128, fee_growth_last: u128, liquidity: u128) -> u128 {
+    let growth = fee_growth_inside.wrapping_sub(fee_growth_last);
+    ((U256::from(growth) * U256::from(liquidity)) >> RESOLUTION).low_u128()
+}
@@ -6,8 +6,10 @@ use frame_support::pallet_prelude::*;
 use frame_support::traits::fungible;
 use frame_support::traits::fungibles;
 use frame_support::PalletId;
+use native_or_asset::NativeOrAssetId;
 use pallet::*;
-use sp_runtime::traits::{AccountIdConversion, CheckedDiv, CheckedMul, IntegerSquareRoot, Zero};
+use sp_runtime::traits::{AccountIdConversion, CheckedDiv, CheckedMul, CheckedSub, One, Zero};
+use sp_runtime::Permill;
 
 // FRAME pallets require their own "mock runtimes" to be able to run unit tests. This module
 // contains a mock runtime specific for testing this pallet's functionality.
@@ -15,15 +17,29 @@ use sp_runtime::traits::{AccountIdConversion, CheckedDiv, CheckedMul, IntegerSqu
 mod mock;
 
 // This module contains the unit tests for this pallet.
-mod liquidity_pool;
 #[cfg(test)]
 mod tests;
 
+pub mod asset_listing;
+mod concentrated_liquidity;
+mod liquidity_pool;
+pub mod native_or_asset;
+pub mod runtime_api;
+mod stable_swap;
+pub mod traits;
+mod weighted_math;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
 // Define type aliases for easier access
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 pub type AssetIdOf<T> = <<T as Config>::Fungibles as fungibles::Inspect<
     <T as frame_system::Config>::AccountId,
 >>::AssetId;
+pub type AssetKindOf<T> = NativeOrAssetId<AssetIdOf<T>>;
 
 pub type BalanceOf<T> = <<T as Config>::NativeBalance as fungible::Inspect<
     <T as frame_system::Config>::AccountId,
@@ -33,17 +49,41 @@ pub type AssetBalanceOf<T> = <<T as Config>::Fungibles as fungibles::Inspect<
     <T as frame_system::Config>::AccountId,
 >>::Balance;
 
+// The log target of the pallet
+const LOG_TARGET: &str = "runtime::dex";
+
+/// The most liquidity token ids a pool creation tries, skipping those already taken, before it
+/// gives up until it is retried
+pub const MAX_LIQUIDITY_TOKEN_ID_ATTEMPTS: u32 = 8;
+
 // All pallet logic is defined in its own module and must be annotated by the `pallet` attribute.
 #[frame_support::pallet]
 pub mod pallet {
     // Import various useful types required by all FRAME pallets.
     use super::*;
+    use crate::asset_listing::AssetListing;
+    use crate::concentrated_liquidity;
     use crate::liquidity_pool::AssetPair;
     use crate::liquidity_pool::LiquidityPool;
+    use crate::liquidity_pool::PoolStatus;
+    use crate::liquidity_pool::ReserveDrift;
+    use crate::liquidity_pool::{Amplification, PoolKind, MAX_AMPLIFICATION_CHANGE, MIN_WEIGHT};
+    use crate::liquidity_pool::{ConcentratedLiquidity, Position, TickMap};
+    use crate::liquidity_pool::{MultiAssetPool, PoolAmounts, PoolAssets};
+    use crate::liquidity_pool::{ObservationBuffer, PriceObservation};
+    use crate::native_or_asset::NativeOrAssets;
+    use crate::traits::{AmmCurve, AssetIdGenerator};
     use frame_support::pallet_prelude::*;
-    use frame_support::traits::fungibles::Mutate;
+    use frame_support::traits::fungible::hold::Mutate as HoldMutate;
+    use frame_support::traits::fungibles::{Create, Destroy, Inspect, Mutate};
     use frame_support::traits::tokens::{Fortitude, Precision, Preservation};
+    use frame_support::weights::Weight;
     use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::{
+        BlakeTwo256, Bounded, Hash as HashT, SaturatedConversion, Saturating,
+    };
+    use sp_runtime::FixedU128;
+    use sp_std::vec::Vec;
 
     // The `Pallet` struct serves as a placeholder to implement traits, methods and dispatchables
     // (`Call`s) in this pallet.
@@ -56,10 +96,13 @@ pub mod pallet {
         /// The overarching runtime event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+        /// The overarching hold reason.
+        type RuntimeHoldReason: From<HoldReason>;
+
         // Type to access the Balances Pallet
         type NativeBalance: fungible::Inspect<Self::AccountId>
             + fungible::Mutate<Self::AccountId>
-            + fungible::hold::Inspect<Self::AccountId>
+            + fungible::hold::Inspect<Self::AccountId, Reason = Self::RuntimeHoldReason>
             + fungible::hold::Mutate<Self::AccountId>
             + fungible::freeze::Inspect<Self::AccountId>
             + fungible::freeze::Mutate<Self::AccountId>;
@@ -67,10 +110,90 @@ pub mod pallet {
         // Type to access the Assets Pallet
         type Fungibles: fungibles::Inspect<Self::AccountId, AssetId = u32>
             + fungibles::Mutate<Self::AccountId>
-            + fungibles::Create<Self::AccountId>;
+            + fungibles::Create<Self::AccountId>
+            + fungibles::Destroy<Self::AccountId>;
 
         #[pallet::constant]
         type PalletId: Get<PalletId>;
+
+        // Type used to allocate the asset ids of liquidity tokens
+        type LiquidityTokenIdGenerator: AssetIdGenerator<AssetIdOf<Self>>;
+
+        /// The amount of liquidity permanently locked in the pool account on the first mint
+        #[pallet::constant]
+        type MinimumLiquidity: Get<AssetBalanceOf<Self>>;
+
+        /// The lowest swap fee a pool can be configured with
+        #[pallet::constant]
+        type MinSwapFee: Get<Permill>;
+
+        /// The highest swap fee a pool can be configured with
+        #[pallet::constant]
+        type MaxSwapFee: Get<Permill>;
+
+        /// The highest share of the swap fee a pool can divert to the protocol
+        #[pallet::constant]
+        type MaxProtocolFee: Get<Permill>;
+
+        /// Origin allowed to create liquidity pools, returning the account paying the deposit
+        type PoolCreateOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+        /// Origin allowed to pause all pools or individual pools
+        type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to change the parameters of existing pools, to destroy pools nobody has
+        /// deposited into yet and to manage the asset allowlist and denylist. Like any other
+        /// origin, it can only destroy pools whose liquidity has all been burned.
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// The account receiving the protocol's share of swap fees
+        #[pallet::constant]
+        type ProtocolFeeReceiver: Get<Self::AccountId>;
+
+        /// The maximum number of assets in the path of a multi-hop swap
+        #[pallet::constant]
+        type MaxSwapPathLength: Get<u32>;
+
+        /// The amount of the native currency held from the creator of a pool until it is
+        /// destroyed
+        #[pallet::constant]
+        type PoolCreationDeposit: Get<BalanceOf<Self>>;
+
+        /// The number of price observations kept for each pool, bounding how far back a
+        /// time-weighted average price can reach
+        #[pallet::constant]
+        type MaxPriceObservations: Get<u32>;
+
+        /// The highest amplification coefficient a StableSwap pool can have
+        #[pallet::constant]
+        type MaxAmplification: Get<u32>;
+
+        /// The fewest blocks a change of the amplification coefficient can be spread over
+        #[pallet::constant]
+        type MinAmplificationRampDuration: Get<BlockNumberFor<Self>>;
+
+        /// The maximum number of assets in a multi-asset pool
+        #[pallet::constant]
+        type MaxAssetsPerPool: Get<u32>;
+
+        /// The maximum number of initialized ticks in a concentrated liquidity pool
+        #[pallet::constant]
+        type MaxInitializedTicks: Get<u32>;
+
+        /// The invariant curves pools can be created with. Runtimes offering more than one curve
+        /// supply an enum of them, each variant delegating to its curve like the mock runtime's
+        /// `TestCurve`. The default curve prices the pools created by `create_liquidity_pool`.
+        type Curve: AmmCurve<AssetBalanceOf<Self>> + Parameter + MaxEncodedLen + Copy + Default;
+
+        /// Weight information for the pallet's extrinsics
+        type WeightInfo: WeightInfo;
+    }
+
+    /// A reason for the pallet placing a hold on funds.
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// The deposit taken for creating a liquidity pool.
+        PoolCreation,
     }
 
     /// A storage map for storing liquidity pools
@@ -81,7 +204,56 @@ pub mod pallet {
     /// Storage map for storing mapping of liquidity token to asset pair
     #[pallet::storage]
     pub type LiquidityTokens<T: Config> =
-        StorageMap<_, Blake2_128Concat, AssetIdOf<T>, (AssetIdOf<T>, AssetIdOf<T>), ValueQuery>;
+        StorageMap<_, Blake2_128Concat, AssetIdOf<T>, AssetPair<T>, OptionQuery>;
+
+    /// The asset id to be used for the liquidity token of the next created pool
+    #[pallet::storage]
+    pub type NextLiquidityTokenId<T: Config> = StorageValue<_, AssetIdOf<T>, OptionQuery>;
+
+    /// The latest cumulative price observations of each liquidity pool
+    #[pallet::storage]
+    pub type PriceObservations<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetPair<T>, ObservationBuffer<T>, ValueQuery>;
+
+    /// The operations allowed on all pools, on top of the status of each pool
+    #[pallet::storage]
+    pub type GlobalStatus<T: Config> = StorageValue<_, PoolStatus, ValueQuery>;
+
+    /// Assets on the allowlist or the denylist
+    #[pallet::storage]
+    pub type AssetListings<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetKindOf<T>, AssetListing, OptionQuery>;
+
+    /// Whether only allowed assets may be traded, otherwise only denied assets are rejected
+    #[pallet::storage]
+    pub type AllowlistEnforced<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// A storage map for storing multi-asset pools, keyed by their sorted assets
+    #[pallet::storage]
+    pub type MultiAssetPools<T: Config> =
+        StorageMap<_, Blake2_128Concat, PoolAssets<T>, MultiAssetPool<T>>;
+
+    /// Storage map for storing mapping of liquidity token to the assets of a multi-asset pool
+    #[pallet::storage]
+    pub type MultiAssetLiquidityTokens<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetIdOf<T>, PoolAssets<T>, OptionQuery>;
+
+    /// The initialized ticks of each concentrated liquidity pool
+    #[pallet::storage]
+    pub type Ticks<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetPair<T>, TickMap<T>, ValueQuery>;
+
+    /// The positions in concentrated liquidity pools, keyed by their owner and their lower and
+    /// upper ticks
+    #[pallet::storage]
+    pub type Positions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AssetPair<T>,
+        Blake2_128Concat,
+        (AccountIdOf<T>, i32, i32),
+        Position<T>,
+    >;
 
     /// Events that functions in this pallet can emit.
     #[pallet::event]
@@ -91,29 +263,38 @@ pub mod pallet {
         /// Parameters:
         /// - `T::AccountId`: The account ID of the liquidity provider who created the pool.
         /// - `(T::AssetId, T::AssetId)`: The trading pair of the created liquidity pool.
-        LiquidityPoolCreated(AccountIdOf<T>, AssetIdOf<T>, AssetIdOf<T>),
+        /// - `T::AssetId`: The ID of the liquidity token created for the pool.
+        LiquidityPoolCreated(AccountIdOf<T>, AssetKindOf<T>, AssetKindOf<T>, AssetIdOf<T>),
 
         /// Liquidity minted.
         /// Parameters:
         /// - `T::AccountId`: The account ID of the liquidity provider who minted the liquidity.
         /// - `(T::AssetId, T::AssetId)`: The trading pair of the liquidity pool.
         /// - `T::Balance`: The amount of liquidity tokens minted.
+        /// - `(T::Balance, T::Balance)`: The amounts of asset A and asset B deposited.
         LiquidityMinted(
             AccountIdOf<T>,
-            AssetIdOf<T>,
-            AssetIdOf<T>,
+            AssetKindOf<T>,
+            AssetKindOf<T>,
+            AssetBalanceOf<T>,
+            AssetBalanceOf<T>,
             AssetBalanceOf<T>,
         ),
 
         /// Liquidity burned.
         /// Parameters:
         /// - `T::AccountId`: The account ID of the liquidity provider who burned the liquidity.
+        /// - `T::AccountId`: The account ID that received the withdrawn assets.
         /// - `(T::AssetId, T::AssetId)`: The trading pair of the liquidity pool.
         /// - `T::Balance`: The amount of liquidity tokens burned.
+        /// - `(T::Balance, T::Balance)`: The amounts of asset A and asset B withdrawn.
         LiquidityBurned(
             AccountIdOf<T>,
-            AssetIdOf<T>,
-            AssetIdOf<T>,
+            AccountIdOf<T>,
+            AssetKindOf<T>,
+            AssetKindOf<T>,
+            AssetBalanceOf<T>,
+            AssetBalanceOf<T>,
             AssetBalanceOf<T>,
         ),
 
@@ -126,11 +307,286 @@ pub mod pallet {
         /// - `T::Balance`: The amount of the asset that was received (bought).
         Swapped(
             AccountIdOf<T>,
-            AssetIdOf<T>,
+            AssetKindOf<T>,
+            AssetBalanceOf<T>,
+            AssetKindOf<T>,
+            AssetBalanceOf<T>,
+        ),
+
+        /// Assets swapped for an exact amount out.
+        /// Parameters:
+        /// - `T::AccountId`: The account ID of the user who performed the swap.
+        /// - `T::AssetId`: The ID of the asset that was swapped (sold).
+        /// - `T::Balance`: The amount of the asset that was swapped (sold).
+        /// - `T::AssetId`: The ID of the asset that was received (bought).
+        /// - `T::Balance`: The amount of the asset that was received (bought).
+        SwappedExactOut(
+            AccountIdOf<T>,
+            AssetKindOf<T>,
+            AssetBalanceOf<T>,
+            AssetKindOf<T>,
+            AssetBalanceOf<T>,
+        ),
+
+        /// Assets swapped along a path of liquidity pools.
+        /// Parameters:
+        /// - `T::AccountId`: The account ID of the user who performed the swap.
+        /// - `Vec<T::AssetId>`: The assets of the path, each hop swaps one for the next.
+        /// - `Vec<T::Balance>`: The amount of each asset of the path going into and out of the
+        ///   hops.
+        SwappedAlongPath(
+            AccountIdOf<T>,
+            BoundedVec<AssetKindOf<T>, T::MaxSwapPathLength>,
+            BoundedVec<AssetBalanceOf<T>, T::MaxSwapPathLength>,
+        ),
+
+        /// Swap fee of a liquidity pool updated.
+        /// Parameters:
+        /// - `(T::AssetId, T::AssetId)`: The trading pair of the liquidity pool.
+        /// - `Permill`: The new swap fee.
+        SwapFeeUpdated(AssetKindOf<T>, AssetKindOf<T>, Permill),
+
+        /// Protocol fee of a liquidity pool updated, a zero fee switches it off.
+        /// Parameters:
+        /// - `(T::AssetId, T::AssetId)`: The trading pair of the liquidity pool.
+        /// - `Permill`: The new share of the swap fee that goes to the protocol.
+        ProtocolFeeUpdated(AssetKindOf<T>, AssetKindOf<T>, Permill),
+
+        /// Protocol fees accrued in a liquidity pool claimed for the protocol fee receiver.
+        /// Parameters:
+        /// - `T::AccountId`: The account ID of the protocol fee receiver.
+        /// - `T::AssetId`: The ID of the asset the fee was collected in.
+        /// - `T::Balance`: The amount of the fee collected.
+        ProtocolFeeCollected(AccountIdOf<T>, AssetKindOf<T>, AssetBalanceOf<T>),
+
+        /// Reserves of a liquidity pool reset to the pool account's holdings.
+        /// Parameters:
+        /// - `(T::AssetId, T::AssetId)`: The trading pair of the liquidity pool.
+        /// - `(T::Balance, T::Balance)`: The new reserves of asset A and asset B.
+        ReservesSynced(
+            AssetKindOf<T>,
+            AssetKindOf<T>,
+            AssetBalanceOf<T>,
+            AssetBalanceOf<T>,
+        ),
+
+        /// Holdings of a pool account in excess of its reserves sent out.
+        /// Parameters:
+        /// - `(T::AssetId, T::AssetId)`: The trading pair of the liquidity pool.
+        /// - `T::AccountId`: The account ID that received the excess.
+        /// - `(T::Balance, T::Balance)`: The amounts of asset A and asset B sent.
+        Skimmed(
+            AssetKindOf<T>,
+            AssetKindOf<T>,
+            AccountIdOf<T>,
+            AssetBalanceOf<T>,
+            AssetBalanceOf<T>,
+        ),
+
+        /// Liquidity pool destroyed.
+        /// Parameters:
+        /// - `(T::AssetId, T::AssetId)`: The trading pair of the destroyed liquidity pool.
+        /// - `T::AssetId`: The ID of the liquidity token being destroyed.
+        LiquidityPoolDestroyed(AssetKindOf<T>, AssetKindOf<T>, AssetIdOf<T>),
+
+        /// Listing of an asset updated.
+        /// Parameters:
+        /// - `T::AssetId`: The ID of the asset.
+        /// - `Option<AssetListing>`: The new listing, `None` removes the asset from both lists.
+        AssetListingUpdated(AssetKindOf<T>, Option<AssetListing>),
+
+        /// Enforcement of the asset allowlist switched on or off.
+        /// Parameters:
+        /// - `bool`: Whether only allowed assets may be traded.
+        AllowlistEnforcementUpdated(bool),
+
+        /// Status of all pools updated.
+        /// Parameters:
+        /// - `PoolStatus`: The operations now allowed on all pools.
+        GlobalStatusUpdated(PoolStatus),
+
+        /// Status of a liquidity pool updated.
+        /// Parameters:
+        /// - `(T::AssetId, T::AssetId)`: The trading pair of the liquidity pool.
+        /// - `PoolStatus`: The operations now allowed on the pool.
+        PoolStatusUpdated(AssetKindOf<T>, AssetKindOf<T>, PoolStatus),
+
+        /// Amplification coefficient of a StableSwap pool started moving.
+        /// Parameters:
+        /// - `(T::AssetId, T::AssetId)`: The trading pair of the liquidity pool.
+        /// - `u32`: The current amplification coefficient.
+        /// - `u32`: The amplification coefficient at the end of the ramp.
+        /// - `BlockNumber`: The block the ramp ends at.
+        AmplificationRampStarted(AssetKindOf<T>, AssetKindOf<T>, u32, u32, BlockNumberFor<T>),
+
+        /// Amplification coefficient of a StableSwap pool stopped at its current value.
+        /// Parameters:
+        /// - `(T::AssetId, T::AssetId)`: The trading pair of the liquidity pool.
+        /// - `u32`: The amplification coefficient the pool keeps.
+        AmplificationRampStopped(AssetKindOf<T>, AssetKindOf<T>, u32),
+
+        /// Multi-asset pool created.
+        /// Parameters:
+        /// - `T::AccountId`: The account ID that created the pool.
+        /// - `Vec<T::AssetId>`: The sorted assets of the pool.
+        /// - `T::AssetId`: The ID of the liquidity token created for the pool.
+        MultiAssetPoolCreated(AccountIdOf<T>, PoolAssets<T>, AssetIdOf<T>),
+
+        /// Liquidity minted in a multi-asset pool.
+        /// Parameters:
+        /// - `T::AccountId`: The account ID of the liquidity provider who minted the liquidity.
+        /// - `Vec<T::AssetId>`: The sorted assets of the pool.
+        /// - `T::Balance`: The amount of liquidity tokens minted.
+        /// - `Vec<T::Balance>`: The amount of each asset deposited, in the order of the assets.
+        MultiAssetLiquidityMinted(
+            AccountIdOf<T>,
+            PoolAssets<T>,
+            AssetBalanceOf<T>,
+            PoolAmounts<T>,
+        ),
+
+        /// Liquidity burned in a multi-asset pool.
+        /// Parameters:
+        /// - `T::AccountId`: The account ID of the liquidity provider who burned the liquidity.
+        /// - `Vec<T::AssetId>`: The sorted assets of the pool.
+        /// - `T::Balance`: The amount of liquidity tokens burned.
+        /// - `Vec<T::Balance>`: The amount of each asset withdrawn, in the order of the assets.
+        MultiAssetLiquidityBurned(
+            AccountIdOf<T>,
+            PoolAssets<T>,
+            AssetBalanceOf<T>,
+            PoolAmounts<T>,
+        ),
+
+        /// Liquidity added to a position in a concentrated liquidity pool.
+        /// Parameters:
+        /// - `T::AccountId`: The account ID of the owner of the position.
+        /// - `T::AssetId`: The ID of the first asset of the pool.
+        /// - `T::AssetId`: The ID of the second asset of the pool.
+        /// - `i32`: The lower tick of the position.
+        /// - `i32`: The upper tick of the position.
+        /// - `u128`: The liquidity added.
+        /// - `T::Balance`: The amount of the first asset deposited.
+        /// - `T::Balance`: The amount of the second asset deposited.
+        PositionIncreased(
+            AccountIdOf<T>,
+            AssetKindOf<T>,
+            AssetKindOf<T>,
+            i32,
+            i32,
+            u128,
+            AssetBalanceOf<T>,
+            AssetBalanceOf<T>,
+        ),
+
+        /// Liquidity removed from a position in a concentrated liquidity pool.
+        /// Parameters:
+        /// - `T::AccountId`: The account ID of the owner of the position.
+        /// - `T::AssetId`: The ID of the first asset of the pool.
+        /// - `T::AssetId`: The ID of the second asset of the pool.
+        /// - `i32`: The lower tick of the position.
+        /// - `i32`: The upper tick of the position.
+        /// - `u128`: The liquidity removed.
+        /// - `T::Balance`: The amount of the first asset owed to the position.
+        /// - `T::Balance`: The amount of the second asset owed to the position.
+        PositionDecreased(
+            AccountIdOf<T>,
+            AssetKindOf<T>,
+            AssetKindOf<T>,
+            i32,
+            i32,
+            u128,
+            AssetBalanceOf<T>,
+            AssetBalanceOf<T>,
+        ),
+
+        /// The amounts owed to a position in a concentrated liquidity pool collected.
+        /// Parameters:
+        /// - `T::AccountId`: The account ID of the owner of the position.
+        /// - `T::AccountId`: The account ID that received the amounts.
+        /// - `T::AssetId`: The ID of the first asset of the pool.
+        /// - `T::AssetId`: The ID of the second asset of the pool.
+        /// - `i32`: The lower tick of the position.
+        /// - `i32`: The upper tick of the position.
+        /// - `T::Balance`: The amount of the first asset collected.
+        /// - `T::Balance`: The amount of the second asset collected.
+        PositionCollected(
+            AccountIdOf<T>,
+            AccountIdOf<T>,
+            AssetKindOf<T>,
+            AssetKindOf<T>,
+            i32,
+            i32,
+            AssetBalanceOf<T>,
+            AssetBalanceOf<T>,
+        ),
+
+        /// Status of a multi-asset pool updated.
+        /// Parameters:
+        /// - `Vec<T::AssetId>`: The sorted assets of the pool.
+        /// - `PoolStatus`: The operations now allowed on the pool.
+        MultiAssetPoolStatusUpdated(PoolAssets<T>, PoolStatus),
+
+        /// Protocol fee of a multi-asset pool updated, a zero fee switches it off.
+        /// Parameters:
+        /// - `Vec<T::AssetId>`: The sorted assets of the pool.
+        /// - `Permill`: The new share of the swap fee that goes to the protocol.
+        MultiAssetProtocolFeeUpdated(PoolAssets<T>, Permill),
+
+        /// Multi-asset pool destroyed.
+        /// Parameters:
+        /// - `Vec<T::AssetId>`: The sorted assets of the destroyed pool.
+        /// - `T::AssetId`: The ID of the liquidity token being destroyed.
+        MultiAssetPoolDestroyed(PoolAssets<T>, AssetIdOf<T>),
+
+        /// No pool was created as the next liquidity token IDs were all taken. They are skipped
+        /// for good, so creating the pool again tries the following ones.
+        /// Parameters:
+        /// - `T::AssetId`: The liquidity token ID the next pool creation starts from.
+        LiquidityTokenIdsSkipped(AssetIdOf<T>),
+
+        /// What a destroyed liquidity pool's account still held, accrued protocol fees included,
+        /// sent to the protocol fee receiver.
+        /// Parameters:
+        /// - `(T::AssetId, T::AssetId)`: The trading pair of the destroyed liquidity pool.
+        /// - `T::AccountId`: The account ID of the protocol fee receiver.
+        /// - `(T::Balance, T::Balance)`: The amounts of asset A and asset B sent.
+        LiquidityPoolSwept(
+            AssetKindOf<T>,
+            AssetKindOf<T>,
+            AccountIdOf<T>,
+            AssetBalanceOf<T>,
             AssetBalanceOf<T>,
-            AssetIdOf<T>,
+        ),
+
+        /// What a destroyed multi-asset pool's account still held, accrued protocol fees
+        /// included, sent to the protocol fee receiver.
+        /// Parameters:
+        /// - `Vec<T::AssetId>`: The sorted assets of the destroyed pool.
+        /// - `T::AccountId`: The account ID of the protocol fee receiver.
+        /// - `Vec<T::Balance>`: The amount of each asset sent, in the order of the assets.
+        MultiAssetPoolSwept(PoolAssets<T>, AccountIdOf<T>, PoolAmounts<T>),
+
+        /// The protocol's share of the fee of a swap accrued in a liquidity pool, deposited
+        /// before the event of the swap.
+        /// Parameters:
+        /// - `(T::AssetId, T::AssetId)`: The trading pair of the liquidity pool.
+        /// - `T::AssetId`: The ID of the asset the fee was paid in.
+        /// - `T::Balance`: The amount of the fee accrued for the protocol.
+        ProtocolFeeAccrued(
+            AssetKindOf<T>,
+            AssetKindOf<T>,
+            AssetKindOf<T>,
             AssetBalanceOf<T>,
         ),
+
+        /// The protocol's share of the fee of a swap accrued in a multi-asset pool, deposited
+        /// before the event of the swap.
+        /// Parameters:
+        /// - `Vec<T::AssetId>`: The sorted assets of the pool.
+        /// - `T::AssetId`: The ID of the asset the fee was paid in.
+        /// - `T::Balance`: The amount of the fee accrued for the protocol.
+        MultiAssetProtocolFeeAccrued(PoolAssets<T>, AssetKindOf<T>, AssetBalanceOf<T>),
     }
 
     /// Errors that can be returned by this pallet.
@@ -177,6 +633,98 @@ pub mod pallet {
 
         /// There is no liquidity to burn
         ZeroLiquidityBurned,
+
+        /// No more liquidity token IDs can be generated
+        LiquidityTokenIdsExhausted,
+
+        /// The first deposit into a pool does not exceed the minimum liquidity
+        InsufficientInitialLiquidity,
+
+        /// The amount of asset A to deposit is below the specified minimum
+        InsufficientAmountA,
+
+        /// The amount of asset B to deposit is below the specified minimum
+        InsufficientAmountB,
+
+        /// The swap fee is outside of the configured bounds
+        InvalidSwapFee,
+
+        /// The amount required to swap in exceeds the maximum specified
+        ExcessiveAmountIn,
+
+        /// The swap path is shorter than two assets or contains an asset more than once
+        InvalidPath,
+
+        /// An amount could not be converted between the native and the asset balance types
+        BalanceConversionFailed,
+
+        /// The block the call had to be included by has passed
+        DeadlineExpired,
+
+        /// Liquidity tokens of the pool are still held outside of the pool account, or positions
+        /// of the pool are still open
+        LiquidityPoolNotEmpty,
+
+        /// The asset is on the denylist
+        AssetDenied,
+
+        /// The allowlist is enforced and the asset is not on it
+        AssetNotAllowed,
+
+        /// The operation is paused for the pool or for all pools
+        PoolPaused,
+
+        /// The StableSwap invariant could not be solved for the pool's reserves
+        InvariantNotConverged,
+
+        /// The liquidity pool is not a StableSwap pool
+        NotStableSwapPool,
+
+        /// The amplification coefficient is zero or above the configured maximum
+        InvalidAmplification,
+
+        /// The amplification ramp ends too early or changes the coefficient too much
+        InvalidAmplificationRamp,
+
+        /// The weights of a weighted pool do not sum to one or one is below the minimum weight
+        InvalidWeights,
+
+        /// A multi-asset pool has fewer than two assets or contains an asset more than once
+        InvalidPoolAssets,
+
+        /// The liquidity pool is not a concentrated liquidity pool
+        NotConcentratedLiquidityPool,
+
+        /// The operation is not supported by concentrated liquidity pools
+        ConcentratedLiquidityPool,
+
+        /// A tick is outside of the tick range, the ticks of a position are not ordered or not
+        /// multiples of the pool's tick spacing, or the tick spacing is zero
+        InvalidTickRange,
+
+        /// The concentrated liquidity pool has no room for another initialized tick
+        TooManyTicks,
+
+        /// The account already has a position between the ticks
+        PositionAlreadyExists,
+
+        /// The account has no position between the ticks
+        PositionNotFound,
+
+        /// The protocol fee is above the configured maximum
+        InvalidProtocolFee,
+
+        /// A pool cannot trade an asset against itself
+        IdenticalAssets,
+
+        /// Only the depositor or the admin origin can destroy a pool that never held liquidity
+        NotPoolDepositor,
+
+        /// The asset does not exist
+        AssetNotFound,
+
+        /// A swap in a concentrated liquidity pool took more steps than it can cross ticks
+        TooManySwapSteps,
     }
 
     /// The pallet's dispatchable functions ([`Call`]s).
@@ -184,80 +732,90 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
         // Dispatchable call to create a new liquidity pool
         #[pallet::call_index(0)]
-        #[pallet::weight(Weight::default())]
+        #[pallet::weight(T::WeightInfo::create_liquidity_pool())]
         pub fn create_liquidity_pool(
             origin: OriginFor<T>,
-            asset_a: AssetIdOf<T>,
-            asset_b: AssetIdOf<T>,
-            liquidity_token: AssetIdOf<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            swap_fee: Permill,
         ) -> DispatchResult {
-            // ensure that the origin has been signed
-            let sender = ensure_signed(origin)?;
-
-            let trading_pair = AssetPair::new(asset_a.clone(), asset_b.clone());
-            ensure!(
-                !LiquidityPools::<T>::contains_key(trading_pair.clone()),
-                Error::<T>::LiquidityPoolAlreadyExists
-            );
-
-            // Create a new liquidity pool
-            let liquidity_pool = LiquidityPool {
-                assets: trading_pair.clone(),
-                reserves: (Zero::zero(), Zero::zero()),
-                total_liquidity: Zero::zero(),
-                liquidity_token,
-            };
-
-            // Insert the new liquidity pool into the storage
-            LiquidityPools::<T>::insert(trading_pair.clone(), liquidity_pool);
+            // ensure that the origin may create pools
+            let sender = T::PoolCreateOrigin::ensure_origin(origin)?;
 
-            // Log an event indicating that the pool was created
-            Self::deposit_event(Event::LiquidityPoolCreated(
+            Self::do_create_pool(
                 sender,
-                trading_pair.asset_a,
-                trading_pair.asset_b,
-            ));
-
-            Ok(())
+                asset_a,
+                asset_b,
+                swap_fee,
+                PoolKind::Curve(T::Curve::default()),
+            )
         }
 
         #[pallet::call_index(1)]
-        #[pallet::weight(Weight::default())]
+        #[pallet::weight(T::WeightInfo::mint_liquidity())]
         pub fn mint_liquidity(
             origin: OriginFor<T>,
-            asset_a: AssetIdOf<T>,
-            asset_b: AssetIdOf<T>,
-            amount_a: AssetBalanceOf<T>,
-            amount_b: AssetBalanceOf<T>,
-            min_liquidity: AssetBalanceOf<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            amount_a_desired: AssetBalanceOf<T>,
+            amount_b_desired: AssetBalanceOf<T>,
+            amount_a_min: AssetBalanceOf<T>,
+            amount_b_min: AssetBalanceOf<T>,
+            deadline: Option<BlockNumberFor<T>>,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
+            Self::ensure_deadline(deadline)?;
 
             let trading_pair = AssetPair::new(asset_a, asset_b);
 
             // Get the liquidity pool from storage
             let mut liquidity_pool =
                 LiquidityPools::<T>::get(&trading_pair).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_pool_allows(&liquidity_pool, PoolStatus::allows_minting)?;
+            Self::update_price_oracle(&trading_pair, &mut liquidity_pool);
 
-            // Calculate the liquidity minted based on the provided amounts and the current reserves
-            let liquidity_minted = Self::calculate_liquidity_minted(
-                (amount_a, amount_b),
+            // Only take as much of each asset as the current pool ratio requires
+            let (amount_a, amount_b) = Self::calculate_optimal_amounts(
+                (amount_a_desired, amount_b_desired),
+                (amount_a_min, amount_b_min),
                 (liquidity_pool.reserves.0, liquidity_pool.reserves.1),
-                liquidity_pool.total_liquidity,
             )?;
 
-            // Ensure that the liquidity minted is greater than or equal to the minimum liquidity specified
+            // Calculate the liquidity minted based on the provided amounts and the current reserves
+            let liquidity_minted =
+                Self::calculate_liquidity_minted((amount_a, amount_b), &liquidity_pool)?;
+
+            // The first mint locks the minimum liquidity away from the depositor
+            let locked_liquidity = if liquidity_pool.total_liquidity.is_zero() {
+                T::MinimumLiquidity::get()
+            } else {
+                Zero::zero()
+            };
+            let user_liquidity = liquidity_minted
+                .checked_sub(&locked_liquidity)
+                .ok_or(Error::<T>::InsufficientInitialLiquidity)?;
+
+            // Ensure that the deposit is large enough to be worth at least one liquidity token
             ensure!(
-                liquidity_minted >= min_liquidity,
+                !user_liquidity.is_zero(),
                 Error::<T>::InsufficientLiquidityMinted
             );
 
             // Transfer the assets from the sender to the liquidity pool
-            Self::transfer_asset_to_pool(&sender, trading_pair.asset_a, amount_a)?;
-            Self::transfer_asset_to_pool(&sender, trading_pair.asset_b, amount_b)?;
+            Self::transfer_asset_to_pool(&trading_pair, &sender, trading_pair.asset_a, amount_a)?;
+            Self::transfer_asset_to_pool(&trading_pair, &sender, trading_pair.asset_b, amount_b)?;
 
             // Mint liquidity tokens to the sender
-            Self::mint_liquidity_tokens(&sender, liquidity_pool.liquidity_token, liquidity_minted)?;
+            Self::mint_liquidity_tokens(&sender, liquidity_pool.liquidity_token, user_liquidity)?;
+
+            // Lock the minimum liquidity in the pool account, which never burns its own tokens
+            if !locked_liquidity.is_zero() {
+                Self::mint_liquidity_tokens(
+                    &Self::pool_account_id(&trading_pair),
+                    liquidity_pool.liquidity_token,
+                    locked_liquidity,
+                )?;
+            }
 
             // Update the liquidity pool reserves and total liquidity using the `mint` method
             liquidity_pool.mint((amount_a, amount_b), liquidity_minted)?;
@@ -270,7 +828,9 @@ pub mod pallet {
                 sender,
                 trading_pair.asset_a,
                 trading_pair.asset_b,
-                liquidity_minted,
+                user_liquidity,
+                amount_a,
+                amount_b,
             ));
 
             Ok(())
@@ -278,29 +838,32 @@ pub mod pallet {
 
         // Dispatchable call to burn liquidity tokens
         #[pallet::call_index(2)]
-        #[pallet::weight(Weight::default())]
+        #[pallet::weight(T::WeightInfo::burn_liquidity())]
         pub fn burn_liquidity(
             origin: OriginFor<T>,
-            asset_a: AssetIdOf<T>,
-            asset_b: AssetIdOf<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
             liquidity_burned: AssetBalanceOf<T>,
             min_amount_a: AssetBalanceOf<T>,
             min_amount_b: AssetBalanceOf<T>,
+            beneficiary: Option<AccountIdOf<T>>,
+            deadline: Option<BlockNumberFor<T>>,
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
+            Self::ensure_deadline(deadline)?;
+            // The withdrawn assets go to the liquidity provider unless another account is given
+            let beneficiary = beneficiary.unwrap_or_else(|| sender.clone());
 
             let trading_pair = AssetPair::new(asset_a, asset_b);
 
             let mut liquidity_pool = LiquidityPools::<T>::get(trading_pair.clone())
                 .ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_pool_allows(&liquidity_pool, PoolStatus::allows_burning)?;
+            Self::update_price_oracle(&trading_pair, &mut liquidity_pool);
 
             // Calculate the amounts of tokens to withdraw based on the liquidity burned and
             // the current reserves
-            let amounts_out = Self::calculate_amounts_out(
-                liquidity_burned,
-                (liquidity_pool.reserves.0, liquidity_pool.reserves.1),
-                liquidity_pool.total_liquidity,
-            )?;
+            let amounts_out = Self::calculate_amounts_out(liquidity_burned, &liquidity_pool)?;
             ensure!(
                 amounts_out.0 >= min_amount_a && amounts_out.1 >= min_amount_b,
                 Error::<T>::InsufficientAmountsOut
@@ -309,40 +872,74 @@ pub mod pallet {
             // Burn the liquidity tokens from the sender
             Self::burn_liquidity_tokens(&sender, liquidity_pool.liquidity_token, liquidity_burned)?;
 
+            // Pay the underlying assets out of the pool to the beneficiary
+            Self::transfer_asset_to_user(
+                &trading_pair,
+                &beneficiary,
+                trading_pair.asset_a,
+                amounts_out.0,
+            )?;
+            Self::transfer_asset_to_user(
+                &trading_pair,
+                &beneficiary,
+                trading_pair.asset_b,
+                amounts_out.1,
+            )?;
+
             // Update the liquidity pool reserves and total liquidity
             liquidity_pool.burn(liquidity_burned, amounts_out)?;
             LiquidityPools::<T>::insert(trading_pair.clone(), liquidity_pool);
 
             Self::deposit_event(Event::LiquidityBurned(
                 sender,
+                beneficiary,
                 trading_pair.asset_a,
                 trading_pair.asset_b,
                 liquidity_burned,
+                amounts_out.0,
+                amounts_out.1,
             ));
 
             Ok(())
         }
 
         #[pallet::call_index(3)]
-        #[pallet::weight(Weight::default())]
+        #[pallet::weight(Pallet::<T>::with_tick_crossings(T::WeightInfo::swap(), 1))]
         pub fn swap(
             origin: OriginFor<T>,
-            asset_in: AssetIdOf<T>,
-            asset_out: AssetIdOf<T>,
+            asset_in: AssetKindOf<T>,
+            asset_out: AssetKindOf<T>,
             amount_in: AssetBalanceOf<T>,
             min_amount_out: AssetBalanceOf<T>,
-        ) -> DispatchResult {
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
+            Self::ensure_deadline(deadline)?;
 
             let trading_pair = AssetPair::new(asset_in, asset_out);
 
             let mut liquidity_pool = LiquidityPools::<T>::get(trading_pair.clone())
                 .ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_pool_allows(&liquidity_pool, PoolStatus::allows_swaps)?;
+            Self::update_price_oracle(&trading_pair, &mut liquidity_pool);
+
+            Self::ensure_tradable(asset_in)?;
+            Self::ensure_tradable(asset_out)?;
+
+            let (amount_out, tick_crossings) = Self::swap_in_pool(
+                &trading_pair,
+                &mut liquidity_pool,
+                (asset_in, amount_in),
+                asset_out,
+                min_amount_out,
+            )?;
 
-            let amount_out = liquidity_pool.swap(asset_in, amount_in, asset_out, min_amount_out)?;
-
-            Self::transfer_asset_from_user(&sender, asset_in, amount_in)?;
-            Self::transfer_asset_to_user(&sender, asset_out, amount_out)?;
+            Self::settle_swap(
+                &trading_pair,
+                &sender,
+                (asset_in, amount_in),
+                (asset_out, amount_out),
+            )?;
 
             LiquidityPools::<T>::insert(&trading_pair, liquidity_pool);
 
@@ -350,31 +947,1834 @@ pub mod pallet {
                 sender, asset_in, amount_in, asset_out, amount_out,
             ));
 
-            Ok(())
+            Ok(Some(T::WeightInfo::swap().saturating_add(tick_crossings)).into())
         }
-    }
 
-    /// The pallet's internal functions.
-    impl<T: Config> Pallet<T> {
-        fn calculate_liquidity_minted(
-            amounts: (AssetBalanceOf<T>, AssetBalanceOf<T>),
-            reserves: (AssetBalanceOf<T>, AssetBalanceOf<T>),
-            total_liquidity: AssetBalanceOf<T>,
-        ) -> Result<AssetBalanceOf<T>, DispatchError> {
-            let (amount_a, amount_b) = amounts;
+        // Dispatchable call to buy an exact amount of one asset with another
+        #[pallet::call_index(6)]
+        #[pallet::weight(Pallet::<T>::with_tick_crossings(T::WeightInfo::swap_exact_out(), 1))]
+        pub fn swap_exact_out(
+            origin: OriginFor<T>,
+            asset_in: AssetKindOf<T>,
+            asset_out: AssetKindOf<T>,
+            amount_out: AssetBalanceOf<T>,
+            max_amount_in: AssetBalanceOf<T>,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_deadline(deadline)?;
+
+            let trading_pair = AssetPair::new(asset_in, asset_out);
+
+            let mut liquidity_pool = LiquidityPools::<T>::get(trading_pair.clone())
+                .ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_pool_allows(&liquidity_pool, PoolStatus::allows_swaps)?;
+            Self::update_price_oracle(&trading_pair, &mut liquidity_pool);
+
+            Self::ensure_tradable(asset_in)?;
+            Self::ensure_tradable(asset_out)?;
+
+            let (amount_in, tick_crossings) = Self::swap_exact_out_in_pool(
+                &trading_pair,
+                &mut liquidity_pool,
+                asset_in,
+                (asset_out, amount_out),
+                max_amount_in,
+            )?;
+
+            Self::settle_swap(
+                &trading_pair,
+                &sender,
+                (asset_in, amount_in),
+                (asset_out, amount_out),
+            )?;
+
+            LiquidityPools::<T>::insert(&trading_pair, liquidity_pool);
+
+            Self::deposit_event(Event::SwappedExactOut(
+                sender, asset_in, amount_in, asset_out, amount_out,
+            ));
+
+            Ok(Some(T::WeightInfo::swap_exact_out().saturating_add(tick_crossings)).into())
+        }
+
+        // Dispatchable call to swap an exact amount through a path of liquidity pools
+        #[pallet::call_index(7)]
+        #[pallet::weight(Pallet::<T>::with_tick_crossings(
+            T::WeightInfo::swap_exact_in_path(path.len() as u32),
+            (path.len() as u32).saturating_sub(1),
+        ))]
+        pub fn swap_exact_in_path(
+            origin: OriginFor<T>,
+            path: BoundedVec<AssetKindOf<T>, T::MaxSwapPathLength>,
+            amount_in: AssetBalanceOf<T>,
+            min_amount_out: AssetBalanceOf<T>,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_deadline(deadline)?;
+
+            let mut pools = Self::path_pools(&path)?;
+            for (trading_pair, liquidity_pool) in pools.iter_mut() {
+                Self::update_price_oracle(trading_pair, liquidity_pool);
+            }
+
+            // Swap hop by hop, only the final amount is checked for slippage
+            let mut amounts = Vec::with_capacity(path.len());
+            amounts.push(amount_in);
+            let mut amount = amount_in;
+            let mut tick_crossings = Weight::zero();
+            for (hop, (trading_pair, liquidity_pool)) in pools.iter_mut().enumerate() {
+                let (amount_out, hop_tick_crossings) = Self::swap_in_pool(
+                    trading_pair,
+                    liquidity_pool,
+                    (path[hop], amount),
+                    path[hop + 1],
+                    Zero::zero(),
+                )?;
+                tick_crossings = tick_crossings.saturating_add(hop_tick_crossings);
+                amounts.push(amount_out);
+                amount = amount_out;
+            }
+            ensure!(amount >= min_amount_out, Error::<T>::InsufficientAmountOut);
+
+            Self::settle_path_swap(&sender, &path, &pools, &amounts)?;
+
+            for (trading_pair, liquidity_pool) in pools {
+                LiquidityPools::<T>::insert(&trading_pair, liquidity_pool);
+            }
+
+            let weight = T::WeightInfo::swap_exact_in_path(path.len() as u32);
+            let amounts = BoundedVec::try_from(amounts).map_err(|_| Error::<T>::InvalidPath)?;
+            Self::deposit_event(Event::SwappedAlongPath(sender, path, amounts));
+
+            Ok(Some(weight.saturating_add(tick_crossings)).into())
+        }
+
+        // Dispatchable call to buy an exact amount through a path of liquidity pools
+        #[pallet::call_index(8)]
+        #[pallet::weight(Pallet::<T>::with_tick_crossings(
+            T::WeightInfo::swap_exact_out_path(path.len() as u32),
+            (path.len() as u32).saturating_sub(1),
+        ))]
+        pub fn swap_exact_out_path(
+            origin: OriginFor<T>,
+            path: BoundedVec<AssetKindOf<T>, T::MaxSwapPathLength>,
+            amount_out: AssetBalanceOf<T>,
+            max_amount_in: AssetBalanceOf<T>,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_deadline(deadline)?;
+
+            let mut pools = Self::path_pools(&path)?;
+            for (trading_pair, liquidity_pool) in pools.iter_mut() {
+                Self::update_price_oracle(trading_pair, liquidity_pool);
+            }
+
+            // Work backwards from the amount out, only the first amount is checked for slippage.
+            // Every pool appears once in the path, so the order of the hops does not matter.
+            let mut amounts = sp_std::vec![Zero::zero(); path.len()];
+            amounts[pools.len()] = amount_out;
+            let mut tick_crossings = Weight::zero();
+            for (hop, (trading_pair, liquidity_pool)) in pools.iter_mut().enumerate().rev() {
+                let (amount_in, hop_tick_crossings) = Self::swap_exact_out_in_pool(
+                    trading_pair,
+                    liquidity_pool,
+                    path[hop],
+                    (path[hop + 1], amounts[hop + 1]),
+                    AssetBalanceOf::<T>::max_value(),
+                )?;
+                tick_crossings = tick_crossings.saturating_add(hop_tick_crossings);
+                amounts[hop] = amount_in;
+            }
+            ensure!(amounts[0] <= max_amount_in, Error::<T>::ExcessiveAmountIn);
+
+            Self::settle_path_swap(&sender, &path, &pools, &amounts)?;
+
+            for (trading_pair, liquidity_pool) in pools {
+                LiquidityPools::<T>::insert(&trading_pair, liquidity_pool);
+            }
+
+            let weight = T::WeightInfo::swap_exact_out_path(path.len() as u32);
+            let amounts = BoundedVec::try_from(amounts).map_err(|_| Error::<T>::InvalidPath)?;
+            Self::deposit_event(Event::SwappedAlongPath(sender, path, amounts));
+
+            Ok(Some(weight.saturating_add(tick_crossings)).into())
+        }
+
+        // Dispatchable call to change the swap fee of an existing liquidity pool
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::set_swap_fee())]
+        pub fn set_swap_fee(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            swap_fee: Permill,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_swap_fee(swap_fee)?;
+
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            LiquidityPools::<T>::try_mutate(&trading_pair, |maybe_pool| -> DispatchResult {
+                let liquidity_pool = maybe_pool
+                    .as_mut()
+                    .ok_or(Error::<T>::LiquidityPoolNotFound)?;
+                liquidity_pool.swap_fee = swap_fee;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::SwapFeeUpdated(
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                swap_fee,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to set the share of the swap fee that goes to the protocol
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::set_protocol_fee())]
+        pub fn set_protocol_fee(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            protocol_fee: Permill,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            ensure!(
+                protocol_fee <= T::MaxProtocolFee::get(),
+                Error::<T>::InvalidProtocolFee
+            );
+
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            LiquidityPools::<T>::try_mutate(&trading_pair, |maybe_pool| -> DispatchResult {
+                let liquidity_pool = maybe_pool
+                    .as_mut()
+                    .ok_or(Error::<T>::LiquidityPoolNotFound)?;
+                liquidity_pool.protocol_fee = protocol_fee;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ProtocolFeeUpdated(
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                protocol_fee,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to reset the reserves of a liquidity pool to what its account holds,
+        // while the pool allows deposits. Concentrated pools track their reserves in positions and
+        // cannot be synced.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::sync())]
+        pub fn sync(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            let mut liquidity_pool =
+                LiquidityPools::<T>::get(&trading_pair).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_not_concentrated(&liquidity_pool)?;
+            Self::ensure_pool_allows(&liquidity_pool, PoolStatus::allows_minting)?;
+            Self::update_price_oracle(&trading_pair, &mut liquidity_pool);
+
+            liquidity_pool.reserves = Self::reserve_holdings(&trading_pair, &liquidity_pool)?;
+            let (reserve_a, reserve_b) = liquidity_pool.reserves;
+            LiquidityPools::<T>::insert(&trading_pair, liquidity_pool);
+
+            Self::deposit_event(Event::ReservesSynced(
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                reserve_a,
+                reserve_b,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to send whatever a pool account holds beyond its reserves to `to`,
+        // while the pool allows withdrawals. Concentrated pools cannot be skimmed.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::skim())]
+        pub fn skim(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            to: AccountIdOf<T>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            let liquidity_pool =
+                LiquidityPools::<T>::get(&trading_pair).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_not_concentrated(&liquidity_pool)?;
+            Self::ensure_pool_allows(&liquidity_pool, PoolStatus::allows_burning)?;
+            let holdings = Self::reserve_holdings(&trading_pair, &liquidity_pool)?;
+            let (excess_a, excess_b) = ReserveDrift::new(holdings, liquidity_pool.reserves).excess;
+
+            if !excess_a.is_zero() {
+                Self::transfer_asset_to_user(&trading_pair, &to, trading_pair.asset_a, excess_a)?;
+            }
+            if !excess_b.is_zero() {
+                Self::transfer_asset_to_user(&trading_pair, &to, trading_pair.asset_b, excess_b)?;
+            }
+
+            Self::deposit_event(Event::Skimmed(
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                to,
+                excess_a,
+                excess_b,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to remove a liquidity pool and start destroying its liquidity token,
+        // once all liquidity outside of the pool account has been burned and no positions are
+        // left. Any signed origin can destroy a pool that has held liquidity, only the depositor
+        // or the admin origin one that never has. Whatever the pool account still holds goes to
+        // the protocol fee receiver and the creation deposit is released.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::destroy_pool())]
+        pub fn destroy_pool(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+        ) -> DispatchResult {
+            let sender = match T::AdminOrigin::try_origin(origin) {
+                Ok(_) => None,
+                Err(origin) => Some(ensure_signed(origin)?),
+            };
+
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            let liquidity_pool =
+                LiquidityPools::<T>::get(&trading_pair).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            let liquidity_token = liquidity_pool.liquidity_token;
+            let pool_account = Self::pool_account_id(&trading_pair);
+
+            // Only the minimum liquidity locked in the pool account may remain, the assets of
+            // liquidity providers are never swept
+            let locked_liquidity = T::Fungibles::balance(liquidity_token, &pool_account);
+            ensure!(
+                liquidity_pool.total_liquidity == locked_liquidity,
+                Error::<T>::LiquidityPoolNotEmpty
+            );
+            // Positions in a concentrated liquidity pool are not backed by liquidity tokens
+            ensure!(
+                Positions::<T>::iter_prefix(&trading_pair).next().is_none(),
+                Error::<T>::LiquidityPoolNotEmpty
+            );
+
+            // A pool nobody has deposited into yet is its depositor's to withdraw
+            if let Some(sender) = sender {
+                ensure!(
+                    sender == liquidity_pool.depositor || !liquidity_pool.total_liquidity.is_zero(),
+                    Error::<T>::NotPoolDepositor
+                );
+            }
+
+            // Empty the pool account so that it can be reaped
+            if !locked_liquidity.is_zero() {
+                Self::burn_liquidity_tokens(&pool_account, liquidity_token, locked_liquidity)?;
+            }
+            let (holding_a, holding_b) = Self::pool_holdings(&trading_pair)?;
+            let receiver = T::ProtocolFeeReceiver::get();
+            if !holding_a.is_zero() {
+                Self::transfer_asset_to_user(
+                    &trading_pair,
+                    &receiver,
+                    trading_pair.asset_a,
+                    holding_a,
+                )?;
+            }
+            if !holding_b.is_zero() {
+                Self::transfer_asset_to_user(
+                    &trading_pair,
+                    &receiver,
+                    trading_pair.asset_b,
+                    holding_b,
+                )?;
+            }
+            Self::deposit_event(Event::LiquidityPoolSwept(
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                receiver,
+                holding_a,
+                holding_b,
+            ));
+            // Other assets sent to the pool account keep it alive, which must not block the
+            // destruction. The account then keeps the provider for them.
+            if let Err(error) = frame_system::Pallet::<T>::dec_providers(&pool_account) {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "pool account {:?} keeps its provider: {:?}",
+                    pool_account,
+                    error,
+                );
+            }
+
+            // The remaining accounts of the liquidity token are cleaned up through the assets
+            // pallet's permissionless destroy calls
+            T::Fungibles::start_destroy(liquidity_token, None)?;
+
+            LiquidityTokens::<T>::remove(liquidity_token);
+            LiquidityPools::<T>::remove(&trading_pair);
+            PriceObservations::<T>::remove(&trading_pair);
+            Ticks::<T>::remove(&trading_pair);
+
+            T::NativeBalance::release(
+                &HoldReason::PoolCreation.into(),
+                &liquidity_pool.depositor,
+                liquidity_pool.deposit,
+                Precision::BestEffort,
+            )?;
+
+            Self::deposit_event(Event::LiquidityPoolDestroyed(
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                liquidity_token,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to put an asset on the allowlist or the denylist, or to remove it
+        // from both with `None`
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::set_asset_listing())]
+        pub fn set_asset_listing(
+            origin: OriginFor<T>,
+            asset: AssetKindOf<T>,
+            listing: Option<AssetListing>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            AssetListings::<T>::set(asset, listing);
+
+            Self::deposit_event(Event::AssetListingUpdated(asset, listing));
+
+            Ok(())
+        }
+
+        // Dispatchable call to switch between trading only allowed assets and trading every
+        // asset that is not denied
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::set_allowlist_enforced())]
+        pub fn set_allowlist_enforced(origin: OriginFor<T>, enforced: bool) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            AllowlistEnforced::<T>::put(enforced);
+
+            Self::deposit_event(Event::AllowlistEnforcementUpdated(enforced));
+
+            Ok(())
+        }
+
+        // Dispatchable call to set the operations allowed on all pools
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::set_global_status())]
+        pub fn set_global_status(origin: OriginFor<T>, status: PoolStatus) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+
+            GlobalStatus::<T>::put(status);
+
+            Self::deposit_event(Event::GlobalStatusUpdated(status));
+
+            Ok(())
+        }
+
+        // Dispatchable call to set the operations allowed on a liquidity pool
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::set_pool_status())]
+        pub fn set_pool_status(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            status: PoolStatus,
+        ) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            LiquidityPools::<T>::try_mutate(&trading_pair, |maybe_pool| -> DispatchResult {
+                let liquidity_pool = maybe_pool
+                    .as_mut()
+                    .ok_or(Error::<T>::LiquidityPoolNotFound)?;
+                liquidity_pool.status = status;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::PoolStatusUpdated(
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                status,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to create a StableSwap pool for assets expected to trade close to one
+        // to one, with the given amplification coefficient
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::create_stable_pool())]
+        pub fn create_stable_pool(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            swap_fee: Permill,
+            amplification: u32,
+        ) -> DispatchResult {
+            let sender = T::PoolCreateOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_amplification(amplification)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let kind = PoolKind::StableSwap(Amplification::constant(amplification, now));
+            Self::do_create_pool(sender, asset_a, asset_b, swap_fee, kind)
+        }
+
+        // Dispatchable call to move the amplification coefficient of a StableSwap pool linearly
+        // to `future_amplification` by `future_block`. Each ramp can change the coefficient by
+        // at most a factor of `MAX_AMPLIFICATION_CHANGE`.
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::ramp_amplification())]
+        pub fn ramp_amplification(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            future_amplification: u32,
+            future_block: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_amplification(future_amplification)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                future_block >= now.saturating_add(T::MinAmplificationRampDuration::get()),
+                Error::<T>::InvalidAmplificationRamp
+            );
+
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            let current = LiquidityPools::<T>::try_mutate(
+                &trading_pair,
+                |maybe_pool| -> Result<u32, DispatchError> {
+                    let liquidity_pool = maybe_pool
+                        .as_mut()
+                        .ok_or(Error::<T>::LiquidityPoolNotFound)?;
+                    let PoolKind::StableSwap(amplification) = &mut liquidity_pool.kind else {
+                        return Err(Error::<T>::NotStableSwapPool.into());
+                    };
+
+                    let current = amplification.at(now);
+                    ensure!(
+                        future_amplification <= current.saturating_mul(MAX_AMPLIFICATION_CHANGE)
+                            && current
+                                <= future_amplification.saturating_mul(MAX_AMPLIFICATION_CHANGE),
+                        Error::<T>::InvalidAmplificationRamp
+                    );
+                    *amplification = Amplification {
+                        initial: current,
+                        future: future_amplification,
+                        initial_block: now,
+                        future_block,
+                    };
+                    Ok(current)
+                },
+            )?;
+
+            Self::deposit_event(Event::AmplificationRampStarted(
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                current,
+                future_amplification,
+                future_block,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to stop the ramp of a StableSwap pool at its current amplification
+        // coefficient
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::stop_amplification_ramp())]
+        pub fn stop_amplification_ramp(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            let current = LiquidityPools::<T>::try_mutate(
+                &trading_pair,
+                |maybe_pool| -> Result<u32, DispatchError> {
+                    let liquidity_pool = maybe_pool
+                        .as_mut()
+                        .ok_or(Error::<T>::LiquidityPoolNotFound)?;
+                    let PoolKind::StableSwap(amplification) = &mut liquidity_pool.kind else {
+                        return Err(Error::<T>::NotStableSwapPool.into());
+                    };
+
+                    let current = amplification.at(now);
+                    *amplification = Amplification::constant(current, now);
+                    Ok(current)
+                },
+            )?;
+
+            Self::deposit_event(Event::AmplificationRampStopped(
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                current,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to create a weighted pool, `weight_a` and `weight_b` being the weights
+        // of `asset_a` and `asset_b`. The weights are fixed for the lifetime of the pool.
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::create_weighted_pool())]
+        pub fn create_weighted_pool(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            swap_fee: Permill,
+            weight_a: Permill,
+            weight_b: Permill,
+        ) -> DispatchResult {
+            let sender = T::PoolCreateOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_weights(weight_a, weight_b)?;
+
+            // Store the weights in the order of the pair's assets
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            let (weight_a, weight_b) = if trading_pair.asset_a == asset_a {
+                (weight_a, weight_b)
+            } else {
+                (weight_b, weight_a)
+            };
+            let kind = PoolKind::Weighted { weight_a, weight_b };
+            Self::do_create_pool(sender, asset_a, asset_b, swap_fee, kind)
+        }
+
+        // Dispatchable call to create a pool of two or more assets of equal weight, keyed by the
+        // sorted assets
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::create_multi_asset_pool(assets.len() as u32))]
+        pub fn create_multi_asset_pool(
+            origin: OriginFor<T>,
+            assets: BoundedVec<AssetKindOf<T>, T::MaxAssetsPerPool>,
+            swap_fee: Permill,
+        ) -> DispatchResult {
+            let sender = T::PoolCreateOrigin::ensure_origin(origin)?;
+
+            let pool_assets = Self::multi_asset_pool_key(assets.into_inner())?;
+            ensure!(
+                !MultiAssetPools::<T>::contains_key(&pool_assets),
+                Error::<T>::LiquidityPoolAlreadyExists
+            );
+            Self::ensure_valid_swap_fee(swap_fee)?;
+            for asset in pool_assets.iter() {
+                Self::ensure_tradable(*asset)?;
+                Self::ensure_asset_exists(*asset)?;
+            }
+            let Some(liquidity_token) = Self::take_liquidity_token_id()? else {
+                return Ok(());
+            };
+
+            // Hold the creation deposit for the lifetime of the pool
+            let deposit = T::PoolCreationDeposit::get();
+            T::NativeBalance::hold(&HoldReason::PoolCreation.into(), &sender, deposit)?;
+
+            Self::create_liquidity_token(liquidity_token)?;
+            MultiAssetLiquidityTokens::<T>::insert(liquidity_token, pool_assets.clone());
+
+            let reserves = PoolAmounts::<T>::try_from(sp_std::vec![
+                Zero::zero();
+                pool_assets.len()
+            ])
+            .map_err(|_| Error::<T>::InvalidPoolAssets)?;
+            let multi_asset_pool = MultiAssetPool {
+                assets: pool_assets.clone(),
+                reserves: reserves.clone(),
+                total_liquidity: Zero::zero(),
+                liquidity_token,
+                swap_fee,
+                protocol_fee: Permill::zero(),
+                protocol_fees: reserves,
+                depositor: sender.clone(),
+                deposit,
+                status: PoolStatus::Active,
+            };
+
+            // Provide for the pool account like for the account of a pool of two assets
+            frame_system::Pallet::<T>::inc_providers(&Self::multi_asset_pool_account_id(
+                &pool_assets,
+            ));
+            MultiAssetPools::<T>::insert(&pool_assets, multi_asset_pool);
+
+            Self::deposit_event(Event::MultiAssetPoolCreated(
+                sender,
+                pool_assets,
+                liquidity_token,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to deposit every asset of a multi-asset pool. The pool is the one of
+        // the given assets, each deposited up to the desired amount at the ratio of the reserves.
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::mint_multi_asset_liquidity(amounts_desired.len() as u32))]
+        pub fn mint_multi_asset_liquidity(
+            origin: OriginFor<T>,
+            amounts_desired: BoundedVec<(AssetKindOf<T>, AssetBalanceOf<T>), T::MaxAssetsPerPool>,
+            min_liquidity: AssetBalanceOf<T>,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_deadline(deadline)?;
+
+            let (pool_assets, amounts_desired) = Self::split_pool_amounts(amounts_desired)?;
+            let mut multi_asset_pool =
+                MultiAssetPools::<T>::get(&pool_assets).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_multi_asset_pool_allows(&multi_asset_pool, PoolStatus::allows_minting)?;
+
+            let (amounts, liquidity_minted) = multi_asset_pool.deposit_amounts(&amounts_desired)?;
+
+            // The first mint locks the minimum liquidity away from the depositor
+            let locked_liquidity = if multi_asset_pool.total_liquidity.is_zero() {
+                T::MinimumLiquidity::get()
+            } else {
+                Zero::zero()
+            };
+            let user_liquidity = liquidity_minted
+                .checked_sub(&locked_liquidity)
+                .ok_or(Error::<T>::InsufficientInitialLiquidity)?;
+            ensure!(
+                !user_liquidity.is_zero() && user_liquidity >= min_liquidity,
+                Error::<T>::InsufficientLiquidityMinted
+            );
+
+            let pool_account = Self::multi_asset_pool_account_id(&pool_assets);
+            for (asset, amount) in pool_assets.iter().zip(amounts.iter()) {
+                Self::transfer_asset(*asset, &sender, &pool_account, *amount)?;
+            }
+
+            let liquidity_token = multi_asset_pool.liquidity_token;
+            Self::mint_liquidity_tokens(&sender, liquidity_token, user_liquidity)?;
+            if !locked_liquidity.is_zero() {
+                Self::mint_liquidity_tokens(&pool_account, liquidity_token, locked_liquidity)?;
+            }
+
+            multi_asset_pool.mint(&amounts, liquidity_minted)?;
+            MultiAssetPools::<T>::insert(&pool_assets, multi_asset_pool);
+
+            let amounts =
+                PoolAmounts::<T>::try_from(amounts).map_err(|_| Error::<T>::InvalidPoolAssets)?;
+            Self::deposit_event(Event::MultiAssetLiquidityMinted(
+                sender,
+                pool_assets,
+                user_liquidity,
+                amounts,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to burn liquidity of a multi-asset pool for a share of every asset.
+        // The pool is the one of the assets given with the minimum amount of each to receive.
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::burn_multi_asset_liquidity(min_amounts_out.len() as u32))]
+        pub fn burn_multi_asset_liquidity(
+            origin: OriginFor<T>,
+            liquidity_burned: AssetBalanceOf<T>,
+            min_amounts_out: BoundedVec<(AssetKindOf<T>, AssetBalanceOf<T>), T::MaxAssetsPerPool>,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_deadline(deadline)?;
+
+            let (pool_assets, min_amounts_out) = Self::split_pool_amounts(min_amounts_out)?;
+            let mut multi_asset_pool =
+                MultiAssetPools::<T>::get(&pool_assets).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_multi_asset_pool_allows(&multi_asset_pool, PoolStatus::allows_burning)?;
+
+            let amounts_out = multi_asset_pool.withdrawal_amounts(liquidity_burned)?;
+            ensure!(
+                amounts_out
+                    .iter()
+                    .zip(min_amounts_out.iter())
+                    .all(|(amount_out, min_amount_out)| amount_out >= min_amount_out),
+                Error::<T>::InsufficientAmountsOut
+            );
+
+            Self::burn_liquidity_tokens(
+                &sender,
+                multi_asset_pool.liquidity_token,
+                liquidity_burned,
+            )?;
+
+            let pool_account = Self::multi_asset_pool_account_id(&pool_assets);
+            for (asset, amount_out) in pool_assets.iter().zip(amounts_out.iter()) {
+                Self::transfer_asset(*asset, &pool_account, &sender, *amount_out)?;
+            }
+
+            multi_asset_pool.burn(liquidity_burned, &amounts_out)?;
+            MultiAssetPools::<T>::insert(&pool_assets, multi_asset_pool);
+
+            let amounts_out = PoolAmounts::<T>::try_from(amounts_out)
+                .map_err(|_| Error::<T>::InvalidPoolAssets)?;
+            Self::deposit_event(Event::MultiAssetLiquidityBurned(
+                sender,
+                pool_assets,
+                liquidity_burned,
+                amounts_out,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to swap between any two assets of a multi-asset pool. Its prices are
+        // not recorded for `twap`, which reads the observations of the pool of a trading pair and
+        // would otherwise mix in the prices of another pool of the same two assets.
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::swap_multi_asset())]
+        pub fn swap_multi_asset(
+            origin: OriginFor<T>,
+            assets: BoundedVec<AssetKindOf<T>, T::MaxAssetsPerPool>,
+            asset_in: AssetKindOf<T>,
+            asset_out: AssetKindOf<T>,
+            amount_in: AssetBalanceOf<T>,
+            min_amount_out: AssetBalanceOf<T>,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_deadline(deadline)?;
+
+            let pool_assets = Self::multi_asset_pool_key(assets.into_inner())?;
+            let mut multi_asset_pool =
+                MultiAssetPools::<T>::get(&pool_assets).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_multi_asset_pool_allows(&multi_asset_pool, PoolStatus::allows_swaps)?;
+
+            Self::ensure_tradable(asset_in)?;
+            Self::ensure_tradable(asset_out)?;
+
+            let accrued_before = multi_asset_pool.protocol_fees.clone();
+            let amount_out =
+                multi_asset_pool.swap(asset_in, amount_in, asset_out, min_amount_out)?;
+
+            let pool_account = Self::multi_asset_pool_account_id(&pool_assets);
+            Self::transfer_asset(asset_in, &sender, &pool_account, amount_in)?;
+            Self::transfer_asset(asset_out, &pool_account, &sender, amount_out)?;
+
+            if let Some(index_in) = multi_asset_pool.position(asset_in) {
+                let protocol_fee = multi_asset_pool.protocol_fees[index_in]
+                    .saturating_sub(accrued_before[index_in]);
+                if !protocol_fee.is_zero() {
+                    Self::deposit_event(Event::MultiAssetProtocolFeeAccrued(
+                        pool_assets.clone(),
+                        asset_in,
+                        protocol_fee,
+                    ));
+                }
+            }
+            MultiAssetPools::<T>::insert(&pool_assets, multi_asset_pool);
+
+            Self::deposit_event(Event::Swapped(
+                sender, asset_in, amount_in, asset_out, amount_out,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to create a concentrated liquidity pool. Ticks are of the price of the
+        // first asset of the sorted pair in the second, the pool starts at `initial_tick` and
+        // positions are bounded by multiples of `tick_spacing`.
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::create_concentrated_pool())]
+        pub fn create_concentrated_pool(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            swap_fee: Permill,
+            tick_spacing: u32,
+            initial_tick: i32,
+        ) -> DispatchResult {
+            let sender = T::PoolCreateOrigin::ensure_origin(origin)?;
+            ensure!(
+                tick_spacing > 0 && tick_spacing <= concentrated_liquidity::MAX_TICK as u32,
+                Error::<T>::InvalidTickRange
+            );
+            let sqrt_price = concentrated_liquidity::sqrt_price_at_tick(initial_tick)
+                .ok_or(Error::<T>::InvalidTickRange)?;
+
+            let kind = PoolKind::Concentrated(ConcentratedLiquidity {
+                sqrt_price,
+                tick: initial_tick,
+                liquidity: 0,
+                tick_spacing,
+                fee_growth_global: (0, 0),
+            });
+            Self::do_create_pool(sender, asset_a, asset_b, swap_fee, kind)
+        }
+
+        // Dispatchable call to open a position providing `liquidity` between two ticks of a
+        // concentrated liquidity pool. Ticks and amounts are in the order of the sorted pair.
+        #[pallet::call_index(25)]
+        #[pallet::weight(T::WeightInfo::open_position())]
+        pub fn open_position(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            tick_lower: i32,
+            tick_upper: i32,
+            liquidity: u128,
+            amount_a_max: AssetBalanceOf<T>,
+            amount_b_max: AssetBalanceOf<T>,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_deadline(deadline)?;
+
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            ensure!(
+                !Positions::<T>::contains_key(&trading_pair, (&sender, tick_lower, tick_upper)),
+                Error::<T>::PositionAlreadyExists
+            );
+
+            Self::increase_position_liquidity(
+                sender,
+                trading_pair,
+                (tick_lower, tick_upper),
+                Position::default(),
+                liquidity,
+                (amount_a_max, amount_b_max),
+            )
+        }
+
+        // Dispatchable call to add liquidity to an open position of a concentrated liquidity pool
+        #[pallet::call_index(26)]
+        #[pallet::weight(T::WeightInfo::increase_position())]
+        pub fn increase_position(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            tick_lower: i32,
+            tick_upper: i32,
+            liquidity: u128,
+            amount_a_max: AssetBalanceOf<T>,
+            amount_b_max: AssetBalanceOf<T>,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_deadline(deadline)?;
+
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            let position = Positions::<T>::get(&trading_pair, (&sender, tick_lower, tick_upper))
+                .ok_or(Error::<T>::PositionNotFound)?;
+
+            Self::increase_position_liquidity(
+                sender,
+                trading_pair,
+                (tick_lower, tick_upper),
+                position,
+                liquidity,
+                (amount_a_max, amount_b_max),
+            )
+        }
+
+        // Dispatchable call to remove liquidity from a position of a concentrated liquidity pool.
+        // The amounts backing it are owed to the position until collected.
+        #[pallet::call_index(27)]
+        #[pallet::weight(T::WeightInfo::decrease_position())]
+        pub fn decrease_position(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            tick_lower: i32,
+            tick_upper: i32,
+            liquidity: u128,
+            amount_a_min: AssetBalanceOf<T>,
+            amount_b_min: AssetBalanceOf<T>,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_deadline(deadline)?;
+            ensure!(liquidity > 0, Error::<T>::ZeroLiquidityBurned);
+            let liquidity_delta =
+                i128::try_from(liquidity).map_err(|_| Error::<T>::InsufficientLiquidity)?;
+
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            let position_id = (sender.clone(), tick_lower, tick_upper);
+            let mut position = Positions::<T>::get(&trading_pair, &position_id)
+                .ok_or(Error::<T>::PositionNotFound)?;
+            let mut liquidity_pool =
+                LiquidityPools::<T>::get(&trading_pair).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_pool_allows(&liquidity_pool, PoolStatus::allows_burning)?;
+
+            let mut ticks = Ticks::<T>::get(&trading_pair);
+            let (amount_a, amount_b) = liquidity_pool.modify_position(
+                &mut ticks,
+                &mut position,
+                (tick_lower, tick_upper),
+                -liquidity_delta,
+            )?;
+            ensure!(
+                amount_a >= amount_a_min && amount_b >= amount_b_min,
+                Error::<T>::InsufficientAmountsOut
+            );
+
+            Ticks::<T>::insert(&trading_pair, ticks);
+            Positions::<T>::insert(&trading_pair, &position_id, position);
+            LiquidityPools::<T>::insert(&trading_pair, liquidity_pool);
+
+            Self::deposit_event(Event::PositionDecreased(
+                sender,
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                tick_lower,
+                tick_upper,
+                liquidity,
+                amount_a,
+                amount_b,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to collect the fees earned by a position of a concentrated liquidity
+        // pool and the amounts removed from it. A position without liquidity is closed once
+        // collected.
+        #[pallet::call_index(28)]
+        #[pallet::weight(T::WeightInfo::collect_position())]
+        pub fn collect_position(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            tick_lower: i32,
+            tick_upper: i32,
+            beneficiary: Option<AccountIdOf<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // The collected amounts go to the owner unless another account is given
+            let beneficiary = beneficiary.unwrap_or_else(|| sender.clone());
+
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            let position_id = (sender.clone(), tick_lower, tick_upper);
+            let mut position = Positions::<T>::get(&trading_pair, &position_id)
+                .ok_or(Error::<T>::PositionNotFound)?;
+            let mut liquidity_pool =
+                LiquidityPools::<T>::get(&trading_pair).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_pool_allows(&liquidity_pool, PoolStatus::allows_burning)?;
+
+            // Credit the fees earned since the position was last updated
+            if position.liquidity > 0 {
+                let mut ticks = Ticks::<T>::get(&trading_pair);
+                liquidity_pool.modify_position(
+                    &mut ticks,
+                    &mut position,
+                    (tick_lower, tick_upper),
+                    0,
+                )?;
+                Ticks::<T>::insert(&trading_pair, ticks);
+            }
+
+            let (amount_a, amount_b) = position.tokens_owed;
+            position.tokens_owed = (Zero::zero(), Zero::zero());
+            Self::transfer_asset_to_user(
+                &trading_pair,
+                &beneficiary,
+                trading_pair.asset_a,
+                amount_a,
+            )?;
+            Self::transfer_asset_to_user(
+                &trading_pair,
+                &beneficiary,
+                trading_pair.asset_b,
+                amount_b,
+            )?;
+
+            // The amounts owed are part of the reserves until collected
+            liquidity_pool.burn(Zero::zero(), (amount_a, amount_b))?;
+            LiquidityPools::<T>::insert(&trading_pair, liquidity_pool);
+            if position.liquidity == 0 {
+                Positions::<T>::remove(&trading_pair, &position_id);
+            } else {
+                Positions::<T>::insert(&trading_pair, &position_id, position);
+            }
+
+            Self::deposit_event(Event::PositionCollected(
+                sender,
+                beneficiary,
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                tick_lower,
+                tick_upper,
+                amount_a,
+                amount_b,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to create a pool priced by one of the curves supported by the runtime
+        #[pallet::call_index(29)]
+        #[pallet::weight(T::WeightInfo::create_curve_pool())]
+        pub fn create_curve_pool(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            swap_fee: Permill,
+            curve: T::Curve,
+        ) -> DispatchResult {
+            let sender = T::PoolCreateOrigin::ensure_origin(origin)?;
+
+            Self::do_create_pool(sender, asset_a, asset_b, swap_fee, PoolKind::Curve(curve))
+        }
+
+        // Dispatchable call to pay the protocol fees accrued in a liquidity pool out to the
+        // protocol fee receiver. Any signed origin can claim them, a claim that the receiver
+        // cannot accept yet leaves them accrued.
+        #[pallet::call_index(30)]
+        #[pallet::weight(T::WeightInfo::claim_protocol_fees())]
+        pub fn claim_protocol_fees(
+            origin: OriginFor<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            let mut liquidity_pool =
+                LiquidityPools::<T>::get(&trading_pair).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            let (fee_a, fee_b) = liquidity_pool.protocol_fees;
+            liquidity_pool.protocol_fees = (Zero::zero(), Zero::zero());
+
+            let receiver = T::ProtocolFeeReceiver::get();
+            for (asset, amount) in [(trading_pair.asset_a, fee_a), (trading_pair.asset_b, fee_b)] {
+                if !amount.is_zero() {
+                    Self::transfer_asset_to_user(&trading_pair, &receiver, asset, amount)?;
+                    Self::deposit_event(Event::ProtocolFeeCollected(
+                        receiver.clone(),
+                        asset,
+                        amount,
+                    ));
+                }
+            }
+
+            LiquidityPools::<T>::insert(&trading_pair, liquidity_pool);
+
+            Ok(())
+        }
+
+        // Dispatchable call to set the operations allowed on a multi-asset pool
+        #[pallet::call_index(31)]
+        #[pallet::weight(T::WeightInfo::set_multi_asset_pool_status())]
+        pub fn set_multi_asset_pool_status(
+            origin: OriginFor<T>,
+            assets: BoundedVec<AssetKindOf<T>, T::MaxAssetsPerPool>,
+            status: PoolStatus,
+        ) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+
+            let pool_assets = Self::multi_asset_pool_key(assets.into_inner())?;
+            MultiAssetPools::<T>::try_mutate(&pool_assets, |maybe_pool| -> DispatchResult {
+                let multi_asset_pool = maybe_pool
+                    .as_mut()
+                    .ok_or(Error::<T>::LiquidityPoolNotFound)?;
+                multi_asset_pool.status = status;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::MultiAssetPoolStatusUpdated(pool_assets, status));
+
+            Ok(())
+        }
+
+        // Dispatchable call to set the share of the swap fee of a multi-asset pool that goes to
+        // the protocol
+        #[pallet::call_index(32)]
+        #[pallet::weight(T::WeightInfo::set_multi_asset_protocol_fee())]
+        pub fn set_multi_asset_protocol_fee(
+            origin: OriginFor<T>,
+            assets: BoundedVec<AssetKindOf<T>, T::MaxAssetsPerPool>,
+            protocol_fee: Permill,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            ensure!(
+                protocol_fee <= T::MaxProtocolFee::get(),
+                Error::<T>::InvalidProtocolFee
+            );
+
+            let pool_assets = Self::multi_asset_pool_key(assets.into_inner())?;
+            MultiAssetPools::<T>::try_mutate(&pool_assets, |maybe_pool| -> DispatchResult {
+                let multi_asset_pool = maybe_pool
+                    .as_mut()
+                    .ok_or(Error::<T>::LiquidityPoolNotFound)?;
+                multi_asset_pool.protocol_fee = protocol_fee;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::MultiAssetProtocolFeeUpdated(
+                pool_assets,
+                protocol_fee,
+            ));
+
+            Ok(())
+        }
+
+        // Dispatchable call to pay the protocol fees accrued in a multi-asset pool out to the
+        // protocol fee receiver, like `claim_protocol_fees` does for a pool of two assets
+        #[pallet::call_index(33)]
+        #[pallet::weight(T::WeightInfo::claim_multi_asset_protocol_fees(assets.len() as u32))]
+        pub fn claim_multi_asset_protocol_fees(
+            origin: OriginFor<T>,
+            assets: BoundedVec<AssetKindOf<T>, T::MaxAssetsPerPool>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let pool_assets = Self::multi_asset_pool_key(assets.into_inner())?;
+            let mut multi_asset_pool =
+                MultiAssetPools::<T>::get(&pool_assets).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+
+            let pool_account = Self::multi_asset_pool_account_id(&pool_assets);
+            let receiver = T::ProtocolFeeReceiver::get();
+            for (asset, accrued) in pool_assets
+                .iter()
+                .zip(multi_asset_pool.protocol_fees.iter_mut())
+            {
+                let amount = sp_std::mem::take(accrued);
+                if !amount.is_zero() {
+                    Self::transfer_asset(*asset, &pool_account, &receiver, amount)?;
+                    Self::deposit_event(Event::ProtocolFeeCollected(
+                        receiver.clone(),
+                        *asset,
+                        amount,
+                    ));
+                }
+            }
+
+            MultiAssetPools::<T>::insert(&pool_assets, multi_asset_pool);
+
+            Ok(())
+        }
+
+        // Dispatchable call to remove a multi-asset pool and start destroying its liquidity
+        // token, with the same requirements as `destroy_pool`. Whatever the pool account still
+        // holds goes to the protocol fee receiver and the creation deposit is released.
+        #[pallet::call_index(34)]
+        #[pallet::weight(T::WeightInfo::destroy_multi_asset_pool(assets.len() as u32))]
+        pub fn destroy_multi_asset_pool(
+            origin: OriginFor<T>,
+            assets: BoundedVec<AssetKindOf<T>, T::MaxAssetsPerPool>,
+        ) -> DispatchResult {
+            let sender = match T::AdminOrigin::try_origin(origin) {
+                Ok(_) => None,
+                Err(origin) => Some(ensure_signed(origin)?),
+            };
+
+            let pool_assets = Self::multi_asset_pool_key(assets.into_inner())?;
+            let multi_asset_pool =
+                MultiAssetPools::<T>::get(&pool_assets).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            let liquidity_token = multi_asset_pool.liquidity_token;
+            let pool_account = Self::multi_asset_pool_account_id(&pool_assets);
+
+            // Only the minimum liquidity locked in the pool account may remain
+            let locked_liquidity = T::Fungibles::balance(liquidity_token, &pool_account);
+            ensure!(
+                multi_asset_pool.total_liquidity == locked_liquidity,
+                Error::<T>::LiquidityPoolNotEmpty
+            );
+
+            // A pool nobody has deposited into yet is its depositor's to withdraw
+            if let Some(sender) = sender {
+                ensure!(
+                    sender == multi_asset_pool.depositor
+                        || !multi_asset_pool.total_liquidity.is_zero(),
+                    Error::<T>::NotPoolDepositor
+                );
+            }
+
+            // Empty the pool account so that it can be reaped
+            if !locked_liquidity.is_zero() {
+                Self::burn_liquidity_tokens(&pool_account, liquidity_token, locked_liquidity)?;
+            }
+            let receiver = T::ProtocolFeeReceiver::get();
+            let mut swept = PoolAmounts::<T>::default();
+            for asset in pool_assets.iter() {
+                let holding = NativeOrAssets::<T>::balance(*asset, &pool_account)?;
+                if !holding.is_zero() {
+                    Self::transfer_asset(*asset, &pool_account, &receiver, holding)?;
+                }
+                swept
+                    .try_push(holding)
+                    .map_err(|_| Error::<T>::InvalidPoolAssets)?;
+            }
+            Self::deposit_event(Event::MultiAssetPoolSwept(
+                pool_assets.clone(),
+                receiver,
+                swept,
+            ));
+            // Other assets sent to the pool account keep it alive, which must not block the
+            // destruction. The account then keeps the provider for them.
+            if let Err(error) = frame_system::Pallet::<T>::dec_providers(&pool_account) {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "multi-asset pool account {:?} keeps its provider: {:?}",
+                    pool_account,
+                    error,
+                );
+            }
+
+            T::Fungibles::start_destroy(liquidity_token, None)?;
+
+            MultiAssetLiquidityTokens::<T>::remove(liquidity_token);
+            MultiAssetPools::<T>::remove(&pool_assets);
+
+            T::NativeBalance::release(
+                &HoldReason::PoolCreation.into(),
+                &multi_asset_pool.depositor,
+                multi_asset_pool.deposit,
+                Precision::BestEffort,
+            )?;
+
+            Self::deposit_event(Event::MultiAssetPoolDestroyed(pool_assets, liquidity_token));
+
+            Ok(())
+        }
+    }
+
+    /// The pallet's internal functions.
+    impl<T: Config> Pallet<T> {
+        /// Returns the amount of `asset_out` received for swapping exactly `amount_in`, as
+        /// `swap` would execute it in the current state.
+        pub fn quote_exact_in(
+            asset_in: AssetKindOf<T>,
+            asset_out: AssetKindOf<T>,
+            amount_in: AssetBalanceOf<T>,
+        ) -> Option<AssetBalanceOf<T>> {
+            let mut liquidity_pool = Self::swappable_pool(asset_in, asset_out).ok()?;
+            let amount_out = if matches!(liquidity_pool.kind, PoolKind::Concentrated(_)) {
+                // Cross the ticks on a copy, the quote must not change them
+                let mut ticks = Ticks::<T>::get(AssetPair::new(asset_in, asset_out));
+                liquidity_pool.swap_concentrated(
+                    &mut ticks,
+                    asset_in,
+                    amount_in,
+                    asset_out,
+                    Zero::zero(),
+                )
+            } else {
+                liquidity_pool.swap(asset_in, amount_in, asset_out, Zero::zero())
+            }
+            .ok()?;
+            Some(amount_out)
+        }
+
+        /// Returns the amount of `asset_in` required to receive exactly `amount_out`, as
+        /// `swap_exact_out` would execute it in the current state.
+        pub fn quote_exact_out(
+            asset_in: AssetKindOf<T>,
+            asset_out: AssetKindOf<T>,
+            amount_out: AssetBalanceOf<T>,
+        ) -> Option<AssetBalanceOf<T>> {
+            let mut liquidity_pool = Self::swappable_pool(asset_in, asset_out).ok()?;
+            let amount_in = if matches!(liquidity_pool.kind, PoolKind::Concentrated(_)) {
+                // Cross the ticks on a copy, the quote must not change them
+                let mut ticks = Ticks::<T>::get(AssetPair::new(asset_in, asset_out));
+                liquidity_pool.swap_concentrated_exact_out(
+                    &mut ticks,
+                    asset_in,
+                    amount_out,
+                    asset_out,
+                    Bounded::max_value(),
+                )
+            } else {
+                liquidity_pool.swap_exact_out(asset_in, amount_out, asset_out, Bounded::max_value())
+            }
+            .ok()?;
+            Some(amount_in)
+        }
+
+        /// Returns the time-weighted average price of `base` in `quote` over the last `window`
+        /// blocks, or `None` if the pool's observations do not reach back that far.
+        pub fn twap(
+            base: AssetKindOf<T>,
+            quote: AssetKindOf<T>,
+            window: BlockNumberFor<T>,
+        ) -> Option<FixedU128> {
+            let trading_pair = AssetPair::new(base, quote);
+            let liquidity_pool = LiquidityPools::<T>::get(&trading_pair)?;
+            if base == quote || window.is_zero() {
+                return None;
+            }
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let start = now.checked_sub(&window)?;
+
+            // The pool's current state is the latest point of its cumulative prices
+            let current = PriceObservation {
+                block_number: now,
+                price_cumulative: liquidity_pool.price_cumulative_at(now),
+            };
+            let observations = PriceObservations::<T>::get(&trading_pair);
+            let points: Vec<_> = observations
+                .ordered()
+                .cloned()
+                .chain(sp_std::iter::once(current.clone()))
+                .collect();
+            let cumulative_start = Self::interpolate_price_cumulative(&points, start)?;
+
+            let (price_end, price_start) = if base == trading_pair.asset_a {
+                (current.price_cumulative.0, cumulative_start.0)
+            } else {
+                (current.price_cumulative.1, cumulative_start.1)
+            };
+            let window: u128 = window.saturated_into();
+            Some(FixedU128::from_inner(
+                price_end.wrapping_sub(price_start) / window,
+            ))
+        }
+
+        /// Returns the reserves of the pool for the pair, ordered like the pair's assets.
+        pub fn get_reserves(
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+        ) -> Option<(AssetBalanceOf<T>, AssetBalanceOf<T>)> {
+            Self::get_pool(asset_a, asset_b).map(|liquidity_pool| liquidity_pool.reserves)
+        }
+
+        /// Returns the pool for the pair.
+        pub fn get_pool(
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+        ) -> Option<LiquidityPool<T>> {
+            LiquidityPools::<T>::get(AssetPair::new(asset_a, asset_b))
+        }
+
+        /// Returns the liquidity token issued by the pool for the pair.
+        pub fn lp_token_for_pair(
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+        ) -> Option<AssetIdOf<T>> {
+            Self::get_pool(asset_a, asset_b).map(|liquidity_pool| liquidity_pool.liquidity_token)
+        }
+
+        /// Returns how far the holdings of a pool account have drifted from its reserves.
+        pub fn reserve_drift(
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+        ) -> Result<ReserveDrift<AssetBalanceOf<T>>, DispatchError> {
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            let liquidity_pool =
+                LiquidityPools::<T>::get(&trading_pair).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            let holdings = Self::reserve_holdings(&trading_pair, &liquidity_pool)?;
+            Ok(ReserveDrift::new(holdings, liquidity_pool.reserves))
+        }
+
+        // Returns the amounts of both assets of the pair held by the pool account beyond the
+        // protocol fees accrued in it
+        fn reserve_holdings(
+            trading_pair: &AssetPair<T>,
+            liquidity_pool: &LiquidityPool<T>,
+        ) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+            let (holding_a, holding_b) = Self::pool_holdings(trading_pair)?;
+            Ok((
+                holding_a.saturating_sub(liquidity_pool.protocol_fees.0),
+                holding_b.saturating_sub(liquidity_pool.protocol_fees.1),
+            ))
+        }
+
+        // Returns the amounts of both assets of the pair held by the pool account
+        fn pool_holdings(
+            trading_pair: &AssetPair<T>,
+        ) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+            let pool_account = Self::pool_account_id(trading_pair);
+            Ok((
+                NativeOrAssets::<T>::balance(trading_pair.asset_a, &pool_account)?,
+                NativeOrAssets::<T>::balance(trading_pair.asset_b, &pool_account)?,
+            ))
+        }
+
+        /// Returns the liquidity pool that issues the given liquidity token, if any.
+        pub fn pool_by_liquidity_token(liquidity_token: AssetIdOf<T>) -> Option<LiquidityPool<T>> {
+            LiquidityTokens::<T>::get(liquidity_token).and_then(LiquidityPools::<T>::get)
+        }
+
+        // Creates a pool of the given kind, holding the creation deposit from `sender`
+        fn do_create_pool(
+            sender: AccountIdOf<T>,
+            asset_a: AssetKindOf<T>,
+            asset_b: AssetKindOf<T>,
+            swap_fee: Permill,
+            kind: PoolKind<BlockNumberFor<T>, T::Curve>,
+        ) -> DispatchResult {
+            ensure!(asset_a != asset_b, Error::<T>::IdenticalAssets);
+            let trading_pair = AssetPair::new(asset_a, asset_b);
+            ensure!(
+                !LiquidityPools::<T>::contains_key(trading_pair.clone()),
+                Error::<T>::LiquidityPoolAlreadyExists
+            );
+            Self::ensure_valid_swap_fee(swap_fee)?;
+            Self::ensure_tradable(asset_a)?;
+            Self::ensure_tradable(asset_b)?;
+            Self::ensure_asset_exists(asset_a)?;
+            Self::ensure_asset_exists(asset_b)?;
+            let Some(liquidity_token) = Self::take_liquidity_token_id()? else {
+                return Ok(());
+            };
+
+            // Hold the creation deposit until the pool is destroyed
+            let deposit = T::PoolCreationDeposit::get();
+            T::NativeBalance::hold(&HoldReason::PoolCreation.into(), &sender, deposit)?;
+
+            // Create the liquidity token, owned by the pallet account
+            Self::create_liquidity_token(liquidity_token)?;
+            LiquidityTokens::<T>::insert(liquidity_token, trading_pair.clone());
+
+            // Create a new liquidity pool
+            let liquidity_pool = LiquidityPool {
+                assets: trading_pair.clone(),
+                reserves: (Zero::zero(), Zero::zero()),
+                total_liquidity: Zero::zero(),
+                liquidity_token,
+                swap_fee,
+                protocol_fee: Permill::zero(),
+                protocol_fees: (Zero::zero(), Zero::zero()),
+                depositor: sender.clone(),
+                deposit,
+                price_cumulative: (0, 0),
+                last_update: frame_system::Pallet::<T>::block_number(),
+                status: PoolStatus::Active,
+                kind,
+            };
+
+            // Start the pool's observations so that time-weighted prices can reach back to it
+            let mut observations = ObservationBuffer::default();
+            observations.push(PriceObservation {
+                block_number: liquidity_pool.last_update,
+                price_cumulative: liquidity_pool.price_cumulative,
+            });
+            PriceObservations::<T>::insert(&trading_pair, observations);
+
+            // The pool account only exists because of this pallet, so provide for it to be able
+            // to hold assets that are not sufficient on their own
+            frame_system::Pallet::<T>::inc_providers(&Self::pool_account_id(&trading_pair));
+
+            // Insert the new liquidity pool into the storage
+            LiquidityPools::<T>::insert(trading_pair.clone(), liquidity_pool);
+
+            // Log an event indicating that the pool was created
+            Self::deposit_event(Event::LiquidityPoolCreated(
+                sender,
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                liquidity_token,
+            ));
+
+            Ok(())
+        }
+
+        // Helper function to take the next free liquidity token id. Generated ids can already be
+        // taken by regularly created assets, so runtimes should pick a generator that leaves
+        // enough room for them. Only a bounded number of taken ids are skipped per call, after
+        // which the cursor still moves past them and `None` is returned. Squatting on the next
+        // ids then only delays pool creation until it is retried.
+        fn take_liquidity_token_id() -> Result<Option<AssetIdOf<T>>, DispatchError> {
+            let mut liquidity_token = NextLiquidityTokenId::<T>::get()
+                .unwrap_or_else(T::LiquidityTokenIdGenerator::initial_id);
+            for _ in 0..MAX_LIQUIDITY_TOKEN_ID_ATTEMPTS {
+                let next_liquidity_token = T::LiquidityTokenIdGenerator::next_id(&liquidity_token)
+                    .ok_or(Error::<T>::LiquidityTokenIdsExhausted)?;
+                let taken = T::Fungibles::asset_exists(liquidity_token)
+                    || LiquidityTokens::<T>::contains_key(liquidity_token)
+                    || MultiAssetLiquidityTokens::<T>::contains_key(liquidity_token);
+                NextLiquidityTokenId::<T>::put(next_liquidity_token);
+                if !taken {
+                    return Ok(Some(liquidity_token));
+                }
+                liquidity_token = next_liquidity_token;
+            }
+
+            Self::deposit_event(Event::LiquidityTokenIdsSkipped(liquidity_token));
+            Ok(None)
+        }
+
+        // Creates the liquidity token of a new pool, owned by the pallet account
+        fn create_liquidity_token(liquidity_token: AssetIdOf<T>) -> DispatchResult {
+            T::Fungibles::create(
+                liquidity_token,
+                Self::pallet_account_id(),
+                false,
+                One::one(),
+            )
+        }
+
+        // Helper function to accumulate the prices of a pool on its first touch in a block, before
+        // its reserves change, and to record an observation of them
+        fn update_price_oracle(trading_pair: &AssetPair<T>, liquidity_pool: &mut LiquidityPool<T>) {
+            let now = frame_system::Pallet::<T>::block_number();
+            if liquidity_pool.update_price_cumulative(now) {
+                PriceObservations::<T>::mutate(trading_pair, |observations| {
+                    observations.push(PriceObservation {
+                        block_number: now,
+                        price_cumulative: liquidity_pool.price_cumulative,
+                    })
+                });
+            }
+        }
+
+        // Helper function to get the cumulative prices at block `at` from the points around it.
+        // The price is constant between two consecutive points, so the result is exact.
+        fn interpolate_price_cumulative(
+            points: &[PriceObservation<T>],
+            at: BlockNumberFor<T>,
+        ) -> Option<(u128, u128)> {
+            points.windows(2).find_map(|segment| {
+                let (before, after) = (&segment[0], &segment[1]);
+                if at < before.block_number || at > after.block_number {
+                    return None;
+                }
+                if at == before.block_number {
+                    return Some(before.price_cumulative);
+                }
+
+                let length: u128 = (after.block_number - before.block_number).saturated_into();
+                let elapsed: u128 = (at - before.block_number).saturated_into();
+                let interpolate = |start: u128, end: u128| {
+                    start.wrapping_add((end.wrapping_sub(start) / length).wrapping_mul(elapsed))
+                };
+                Some((
+                    interpolate(before.price_cumulative.0, after.price_cumulative.0),
+                    interpolate(before.price_cumulative.1, after.price_cumulative.1),
+                ))
+            })
+        }
+
+        // Helper function to reject an operation that the status of the pool or the global
+        // status does not allow
+        fn ensure_pool_allows(
+            liquidity_pool: &LiquidityPool<T>,
+            allows: fn(&PoolStatus) -> bool,
+        ) -> DispatchResult {
+            ensure!(
+                allows(&GlobalStatus::<T>::get()) && allows(&liquidity_pool.status),
+                Error::<T>::PoolPaused
+            );
+            Ok(())
+        }
+
+        // Helper function to reject operations that would break the reserves tracked by the ticks
+        // and positions of a concentrated liquidity pool
+        fn ensure_not_concentrated(liquidity_pool: &LiquidityPool<T>) -> DispatchResult {
+            ensure!(
+                !matches!(liquidity_pool.kind, PoolKind::Concentrated(_)),
+                Error::<T>::ConcentratedLiquidityPool
+            );
+            Ok(())
+        }
+
+        // Helper function to reject an operation that the status of the multi-asset pool or the
+        // global status does not allow
+        fn ensure_multi_asset_pool_allows(
+            multi_asset_pool: &MultiAssetPool<T>,
+            allows: fn(&PoolStatus) -> bool,
+        ) -> DispatchResult {
+            ensure!(
+                allows(&GlobalStatus::<T>::get()) && allows(&multi_asset_pool.status),
+                Error::<T>::PoolPaused
+            );
+            Ok(())
+        }
+
+        // Helper function to get the pool of two assets if a swap between them would pass the
+        // pause and listing checks of the swap calls
+        fn swappable_pool(
+            asset_in: AssetKindOf<T>,
+            asset_out: AssetKindOf<T>,
+        ) -> Result<LiquidityPool<T>, DispatchError> {
+            let liquidity_pool =
+                Self::get_pool(asset_in, asset_out).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_pool_allows(&liquidity_pool, PoolStatus::allows_swaps)?;
+            Self::ensure_tradable(asset_in)?;
+            Self::ensure_tradable(asset_out)?;
+            Ok(liquidity_pool)
+        }
+
+        // Helper function to reject assets that are denied, or not allowed while the allowlist
+        // is enforced
+        fn ensure_tradable(asset: AssetKindOf<T>) -> DispatchResult {
+            match AssetListings::<T>::get(asset) {
+                Some(AssetListing::Denied) => Err(Error::<T>::AssetDenied.into()),
+                Some(AssetListing::Allowed) => Ok(()),
+                None => {
+                    ensure!(!AllowlistEnforced::<T>::get(), Error::<T>::AssetNotAllowed);
+                    Ok(())
+                }
+            }
+        }
+
+        // Helper function to reject pools of assets that were never created
+        fn ensure_asset_exists(asset: AssetKindOf<T>) -> DispatchResult {
+            ensure!(
+                NativeOrAssets::<T>::asset_exists(asset),
+                Error::<T>::AssetNotFound
+            );
+            Ok(())
+        }
+
+        // Helper function to reject calls included after their deadline block
+        fn ensure_deadline(deadline: Option<BlockNumberFor<T>>) -> DispatchResult {
+            if let Some(deadline) = deadline {
+                ensure!(
+                    frame_system::Pallet::<T>::block_number() <= deadline,
+                    Error::<T>::DeadlineExpired
+                );
+            }
+            Ok(())
+        }
+
+        fn ensure_valid_amplification(amplification: u32) -> DispatchResult {
+            ensure!(
+                amplification > 0 && amplification <= T::MaxAmplification::get(),
+                Error::<T>::InvalidAmplification
+            );
+            Ok(())
+        }
+
+        fn ensure_valid_weights(weight_a: Permill, weight_b: Permill) -> DispatchResult {
+            ensure!(
+                weight_a >= MIN_WEIGHT
+                    && weight_b >= MIN_WEIGHT
+                    && weight_a.deconstruct() + weight_b.deconstruct()
+                        == Permill::one().deconstruct(),
+                Error::<T>::InvalidWeights
+            );
+            Ok(())
+        }
+
+        fn ensure_valid_swap_fee(swap_fee: Permill) -> DispatchResult {
+            ensure!(
+                swap_fee >= T::MinSwapFee::get() && swap_fee <= T::MaxSwapFee::get(),
+                Error::<T>::InvalidSwapFee
+            );
+            Ok(())
+        }
+
+        // Helper function to sort the assets of a multi-asset pool into the key of the pool,
+        // rejecting fewer than two assets or duplicates
+        fn multi_asset_pool_key(
+            mut assets: Vec<AssetKindOf<T>>,
+        ) -> Result<PoolAssets<T>, DispatchError> {
+            assets.sort();
+            ensure!(
+                assets.len() >= 2 && assets.windows(2).all(|pair| pair[0] != pair[1]),
+                Error::<T>::InvalidPoolAssets
+            );
+            PoolAssets::<T>::try_from(assets).map_err(|_| Error::<T>::InvalidPoolAssets.into())
+        }
+
+        // Helper function to split amounts given per asset into the key of their multi-asset pool
+        // and the amounts in the order of its assets
+        fn split_pool_amounts(
+            amounts: BoundedVec<(AssetKindOf<T>, AssetBalanceOf<T>), T::MaxAssetsPerPool>,
+        ) -> Result<(PoolAssets<T>, Vec<AssetBalanceOf<T>>), DispatchError> {
+            let mut amounts = amounts.into_inner();
+            amounts.sort_by_key(|(asset, _)| *asset);
+            let (assets, amounts): (Vec<_>, Vec<_>) = amounts.into_iter().unzip();
+            Ok((Self::multi_asset_pool_key(assets)?, amounts))
+        }
+
+        fn calculate_optimal_amounts(
+            desired: (AssetBalanceOf<T>, AssetBalanceOf<T>),
+            min: (AssetBalanceOf<T>, AssetBalanceOf<T>),
+            reserves: (AssetBalanceOf<T>, AssetBalanceOf<T>),
+        ) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+            let (amount_a_desired, amount_b_desired) = desired;
+            let (amount_a_min, amount_b_min) = min;
             let (reserve_a, reserve_b) = reserves;
 
+            // An empty pool accepts any ratio, the first depositor sets the price
+            if reserve_a.is_zero() && reserve_b.is_zero() {
+                return Ok((amount_a_desired, amount_b_desired));
+            }
+
+            let amount_b_optimal = Self::quote(amount_a_desired, reserve_a, reserve_b)?;
+            if amount_b_optimal <= amount_b_desired {
+                ensure!(
+                    amount_b_optimal >= amount_b_min,
+                    Error::<T>::InsufficientAmountB
+                );
+                Ok((amount_a_desired, amount_b_optimal))
+            } else {
+                let amount_a_optimal = Self::quote(amount_b_desired, reserve_b, reserve_a)?;
+                ensure!(
+                    amount_a_optimal <= amount_a_desired,
+                    Error::<T>::InsufficientAmountA
+                );
+                ensure!(
+                    amount_a_optimal >= amount_a_min,
+                    Error::<T>::InsufficientAmountA
+                );
+                Ok((amount_a_optimal, amount_b_desired))
+            }
+        }
+
+        // Returns the amount of the other asset worth `amount` at the current pool ratio
+        fn quote(
+            amount: AssetBalanceOf<T>,
+            reserve_in: AssetBalanceOf<T>,
+            reserve_out: AssetBalanceOf<T>,
+        ) -> Result<AssetBalanceOf<T>, DispatchError> {
+            let amount_out = amount
+                .checked_mul(&reserve_out)
+                .ok_or(Error::<T>::ArithmeticOverflow)?
+                .checked_div(&reserve_in)
+                .ok_or(Error::<T>::DivisionByZero)?;
+            Ok(amount_out)
+        }
+
+        fn calculate_liquidity_minted(
+            amounts: (AssetBalanceOf<T>, AssetBalanceOf<T>),
+            liquidity_pool: &LiquidityPool<T>,
+        ) -> Result<AssetBalanceOf<T>, DispatchError> {
+            let (amount_a, amount_b) = amounts;
+            let (reserve_a, reserve_b) = liquidity_pool.reserves;
+            let total_liquidity = liquidity_pool.total_liquidity;
+
             ensure!(
                 !amount_a.is_zero() && !amount_b.is_zero(),
                 Error::<T>::InsufficientLiquidityMinted
             );
 
-            if total_liquidity.is_zero() {
-                // If the liquidity pool is empty, the minted liquidity is the geometric mean of the amounts
-                let liquidity_minted = Self::geometric_mean(amount_a, amount_b)?;
+            if let PoolKind::Curve(curve) = liquidity_pool.kind {
+                // Pools of a runtime curve mint the shares the curve prices the deposit at
+                let liquidity_minted =
+                    curve.mint_shares(amounts, liquidity_pool.reserves, total_liquidity)?;
+                ensure!(
+                    !total_liquidity.is_zero() || liquidity_minted > T::MinimumLiquidity::get(),
+                    Error::<T>::InsufficientInitialLiquidity
+                );
+                Ok(liquidity_minted)
+            } else if total_liquidity.is_zero() {
+                // If the liquidity pool is empty, the minted liquidity is the pool's invariant of
+                // the amounts
+                let liquidity_minted = liquidity_pool.invariant(amounts)?;
+                ensure!(
+                    liquidity_minted > T::MinimumLiquidity::get(),
+                    Error::<T>::InsufficientInitialLiquidity
+                );
                 Ok(liquidity_minted)
             } else {
-                // If the liquidity pool is not empty, calculate the minted liquidity proportionally
+                // If the liquidity pool is not empty, calculate the minted liquidity proportionally,
+                // which holds for every kind of pool as deposits keep the ratio of the reserves
                 let liquidity_minted_a = amount_a
                     .checked_mul(&total_liquidity)
                     .ok_or(Error::<T>::ArithmeticOverflow)?
@@ -393,31 +2793,33 @@ pub mod pallet {
             }
         }
 
-        fn geometric_mean(
-            amount_a: AssetBalanceOf<T>,
-            amount_b: AssetBalanceOf<T>,
-        ) -> Result<AssetBalanceOf<T>, DispatchError> {
-            let sqrt_product = (amount_a
-                .checked_mul(&amount_b)
-                .ok_or(Error::<T>::ArithmeticOverflow)?)
-            .integer_sqrt();
-            Ok(sqrt_product)
-        }
-
         fn pallet_account_id() -> T::AccountId {
             T::PalletId::get().into_account_truncating()
         }
 
+        /// Returns the account holding the reserves of the pool for the given asset pair.
+        pub fn pool_account_id(trading_pair: &AssetPair<T>) -> T::AccountId {
+            T::PalletId::get().into_sub_account_truncating(trading_pair)
+        }
+
+        /// Returns the account holding the reserves of the multi-asset pool for the given assets.
+        /// The assets are hashed so that pools sharing a prefix of many assets do not collide
+        /// once truncated to an account id.
+        pub fn multi_asset_pool_account_id(assets: &PoolAssets<T>) -> T::AccountId {
+            T::PalletId::get().into_sub_account_truncating(BlakeTwo256::hash_of(assets))
+        }
+
         fn transfer_asset_to_pool(
+            trading_pair: &AssetPair<T>,
             sender: &AccountIdOf<T>,
-            asset_id: AssetIdOf<T>,
+            asset_id: AssetKindOf<T>,
             amount: AssetBalanceOf<T>,
         ) -> DispatchResult {
             // Transfer the asset from the sender to the pool account
-            T::Fungibles::transfer(
+            NativeOrAssets::<T>::transfer(
                 asset_id,
                 sender,
-                &Self::pallet_account_id(),
+                &Self::pool_account_id(trading_pair),
                 amount,
                 Preservation::Expendable,
             )?;
@@ -453,21 +2855,31 @@ pub mod pallet {
 
         fn calculate_amounts_out(
             liquidity_burned: AssetBalanceOf<T>,
-            reserves: (AssetBalanceOf<T>, AssetBalanceOf<T>),
-            total_liquidity: AssetBalanceOf<T>,
+            liquidity_pool: &LiquidityPool<T>,
         ) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+            let total_liquidity = liquidity_pool.total_liquidity;
             ensure!(!liquidity_burned.is_zero(), Error::<T>::ZeroLiquidityBurned);
             ensure!(
                 !total_liquidity.is_zero(),
                 Error::<T>::InsufficientLiquidity
             );
 
-            let (reserve_a, reserve_b) = reserves;
+            let (reserve_a, reserve_b) = liquidity_pool.reserves;
             ensure!(
                 !reserve_a.is_zero() && !reserve_b.is_zero(),
                 Error::<T>::InsufficientLiquidity
             );
 
+            // Pools of a runtime curve pay out what the curve prices the shares at, withdrawals
+            // from the other kinds of pool are proportional to the reserves
+            if let PoolKind::Curve(curve) = liquidity_pool.kind {
+                return curve.burn_amounts(
+                    liquidity_burned,
+                    liquidity_pool.reserves,
+                    total_liquidity,
+                );
+            }
+
             let amount_a = liquidity_burned
                 .checked_mul(&reserve_a)
                 .ok_or(Error::<T>::ArithmeticOverflow)?
@@ -484,28 +2896,284 @@ pub mod pallet {
         }
 
         fn transfer_asset_from_user(
+            trading_pair: &AssetPair<T>,
             user: &AccountIdOf<T>,
-            asset_id: AssetIdOf<T>,
+            asset_id: AssetKindOf<T>,
             amount: AssetBalanceOf<T>,
         ) -> DispatchResult {
-            T::Fungibles::transfer(
+            NativeOrAssets::<T>::transfer(
                 asset_id,
                 user,
-                &Self::pallet_account_id(),
+                &Self::pool_account_id(trading_pair),
                 amount,
                 Preservation::Expendable,
             )?;
             Ok(())
         }
 
+        // Helper function to add to the weight of a swap through `hops` pools the cost of crossing
+        // every initialized tick of each, as any of them may be a concentrated liquidity pool. The
+        // swap refunds what the ticks it actually crosses do not cost.
+        fn with_tick_crossings(weight: Weight, hops: u32) -> Weight {
+            let tick_crossings = Self::tick_crossings_weight(T::MaxInitializedTicks::get());
+            weight.saturating_add(tick_crossings.saturating_mul(hops.into()))
+        }
+
+        // Helper function to compute the cost of crossing `ticks_crossed` ticks on top of a swap
+        fn tick_crossings_weight(ticks_crossed: u32) -> Weight {
+            T::WeightInfo::swap_concentrated(ticks_crossed).saturating_sub(T::WeightInfo::swap())
+        }
+
+        // Helper function to compute the cost of the initialized ticks a concentrated liquidity
+        // swap crossed moving from `tick_before` to `tick_after`
+        fn crossed_ticks_weight(ticks: &TickMap<T>, tick_before: i32, tick_after: i32) -> Weight {
+            let (lower, upper) = if tick_before <= tick_after {
+                (tick_before, tick_after)
+            } else {
+                (tick_after, tick_before)
+            };
+            let ticks_crossed = ticks.range(lower.saturating_add(1)..=upper).count() as u32;
+            Self::tick_crossings_weight(ticks_crossed)
+        }
+
+        // Helper function to swap an exact amount in against the pool of `trading_pair`, storing
+        // the ticks a concentrated liquidity pool crosses. Returns the amount out and the weight
+        // of the ticks crossed.
+        fn swap_in_pool(
+            trading_pair: &AssetPair<T>,
+            liquidity_pool: &mut LiquidityPool<T>,
+            (asset_in, amount_in): (AssetKindOf<T>, AssetBalanceOf<T>),
+            asset_out: AssetKindOf<T>,
+            min_amount_out: AssetBalanceOf<T>,
+        ) -> Result<(AssetBalanceOf<T>, Weight), DispatchError> {
+            let accrued_before = liquidity_pool.protocol_fees;
+            let (amount_out, tick_crossings) =
+                if let PoolKind::Concentrated(state) = liquidity_pool.kind {
+                    let mut ticks = Ticks::<T>::get(trading_pair);
+                    let amount_out = liquidity_pool.swap_concentrated(
+                        &mut ticks,
+                        asset_in,
+                        amount_in,
+                        asset_out,
+                        min_amount_out,
+                    )?;
+                    let tick_after = liquidity_pool.concentrated()?.tick;
+                    let tick_crossings = Self::crossed_ticks_weight(&ticks, state.tick, tick_after);
+                    Ticks::<T>::insert(trading_pair, ticks);
+                    (amount_out, tick_crossings)
+                } else {
+                    let amount_out =
+                        liquidity_pool.swap(asset_in, amount_in, asset_out, min_amount_out)?;
+                    (amount_out, Weight::zero())
+                };
+            Self::deposit_protocol_fee_accrued(
+                trading_pair,
+                liquidity_pool,
+                accrued_before,
+                asset_in,
+            );
+            Ok((amount_out, tick_crossings))
+        }
+
+        // Helper function to swap for an exact amount out against the pool of `trading_pair`,
+        // storing the ticks a concentrated liquidity pool crosses. Returns the amount in and the
+        // weight of the ticks crossed.
+        fn swap_exact_out_in_pool(
+            trading_pair: &AssetPair<T>,
+            liquidity_pool: &mut LiquidityPool<T>,
+            asset_in: AssetKindOf<T>,
+            (asset_out, amount_out): (AssetKindOf<T>, AssetBalanceOf<T>),
+            max_amount_in: AssetBalanceOf<T>,
+        ) -> Result<(AssetBalanceOf<T>, Weight), DispatchError> {
+            let accrued_before = liquidity_pool.protocol_fees;
+            let (amount_in, tick_crossings) =
+                if let PoolKind::Concentrated(state) = liquidity_pool.kind {
+                    let mut ticks = Ticks::<T>::get(trading_pair);
+                    let amount_in = liquidity_pool.swap_concentrated_exact_out(
+                        &mut ticks,
+                        asset_in,
+                        amount_out,
+                        asset_out,
+                        max_amount_in,
+                    )?;
+                    let tick_after = liquidity_pool.concentrated()?.tick;
+                    let tick_crossings = Self::crossed_ticks_weight(&ticks, state.tick, tick_after);
+                    Ticks::<T>::insert(trading_pair, ticks);
+                    (amount_in, tick_crossings)
+                } else {
+                    let amount_in = liquidity_pool.swap_exact_out(
+                        asset_in,
+                        amount_out,
+                        asset_out,
+                        max_amount_in,
+                    )?;
+                    (amount_in, Weight::zero())
+                };
+            Self::deposit_protocol_fee_accrued(
+                trading_pair,
+                liquidity_pool,
+                accrued_before,
+                asset_in,
+            );
+            Ok((amount_in, tick_crossings))
+        }
+
+        // Helper function to report the protocol fee a swap paying in `asset_in` has accrued in
+        // the pool, given the fees accrued before the swap
+        fn deposit_protocol_fee_accrued(
+            trading_pair: &AssetPair<T>,
+            liquidity_pool: &LiquidityPool<T>,
+            accrued_before: (AssetBalanceOf<T>, AssetBalanceOf<T>),
+            asset_in: AssetKindOf<T>,
+        ) {
+            let protocol_fee = if asset_in == trading_pair.asset_a {
+                liquidity_pool
+                    .protocol_fees
+                    .0
+                    .saturating_sub(accrued_before.0)
+            } else {
+                liquidity_pool
+                    .protocol_fees
+                    .1
+                    .saturating_sub(accrued_before.1)
+            };
+            if !protocol_fee.is_zero() {
+                Self::deposit_event(Event::ProtocolFeeAccrued(
+                    trading_pair.asset_a,
+                    trading_pair.asset_b,
+                    asset_in,
+                    protocol_fee,
+                ));
+            }
+        }
+
+        // Helper function to add liquidity to a position of a concentrated liquidity pool, paying
+        // at most `max_amounts` of the pair's assets for it
+        fn increase_position_liquidity(
+            owner: AccountIdOf<T>,
+            trading_pair: AssetPair<T>,
+            (tick_lower, tick_upper): (i32, i32),
+            mut position: Position<T>,
+            liquidity: u128,
+            max_amounts: (AssetBalanceOf<T>, AssetBalanceOf<T>),
+        ) -> DispatchResult {
+            ensure!(liquidity > 0, Error::<T>::InsufficientLiquidityMinted);
+            let liquidity_delta =
+                i128::try_from(liquidity).map_err(|_| Error::<T>::LiquidityOverflow)?;
+
+            let mut liquidity_pool =
+                LiquidityPools::<T>::get(&trading_pair).ok_or(Error::<T>::LiquidityPoolNotFound)?;
+            Self::ensure_pool_allows(&liquidity_pool, PoolStatus::allows_minting)?;
+
+            let mut ticks = Ticks::<T>::get(&trading_pair);
+            let (amount_a, amount_b) = liquidity_pool.modify_position(
+                &mut ticks,
+                &mut position,
+                (tick_lower, tick_upper),
+                liquidity_delta,
+            )?;
+            ensure!(
+                amount_a <= max_amounts.0 && amount_b <= max_amounts.1,
+                Error::<T>::ExcessiveAmountIn
+            );
+
+            Self::transfer_asset_to_pool(&trading_pair, &owner, trading_pair.asset_a, amount_a)?;
+            Self::transfer_asset_to_pool(&trading_pair, &owner, trading_pair.asset_b, amount_b)?;
+
+            Ticks::<T>::insert(&trading_pair, ticks);
+            Positions::<T>::insert(
+                &trading_pair,
+                (owner.clone(), tick_lower, tick_upper),
+                position,
+            );
+            LiquidityPools::<T>::insert(&trading_pair, liquidity_pool);
+
+            Self::deposit_event(Event::PositionIncreased(
+                owner,
+                trading_pair.asset_a,
+                trading_pair.asset_b,
+                tick_lower,
+                tick_upper,
+                liquidity,
+                amount_a,
+                amount_b,
+            ));
+
+            Ok(())
+        }
+
+        // Moves the assets of a swap priced by the pool between the user and the pool account,
+        // which also keeps the protocol fee until it is claimed
+        fn settle_swap(
+            trading_pair: &AssetPair<T>,
+            user: &AccountIdOf<T>,
+            (asset_in, amount_in): (AssetKindOf<T>, AssetBalanceOf<T>),
+            (asset_out, amount_out): (AssetKindOf<T>, AssetBalanceOf<T>),
+        ) -> DispatchResult {
+            Self::transfer_asset_from_user(trading_pair, user, asset_in, amount_in)?;
+            Self::transfer_asset_to_user(trading_pair, user, asset_out, amount_out)?;
+            Ok(())
+        }
+
+        // Loads the liquidity pools traded against by each hop of a swap path
+        fn path_pools(
+            path: &[AssetKindOf<T>],
+        ) -> Result<Vec<(AssetPair<T>, LiquidityPool<T>)>, DispatchError> {
+            ensure!(path.len() >= 2, Error::<T>::InvalidPath);
+            for (index, asset) in path.iter().enumerate() {
+                ensure!(!path[index + 1..].contains(asset), Error::<T>::InvalidPath);
+                Self::ensure_tradable(*asset)?;
+            }
+
+            path.windows(2)
+                .map(|hop| -> Result<_, DispatchError> {
+                    let trading_pair = AssetPair::new(hop[0], hop[1]);
+                    let liquidity_pool = LiquidityPools::<T>::get(&trading_pair)
+                        .ok_or(Error::<T>::LiquidityPoolNotFound)?;
+                    Self::ensure_pool_allows(&liquidity_pool, PoolStatus::allows_swaps)?;
+                    Ok((trading_pair, liquidity_pool))
+                })
+                .collect()
+        }
+
+        // Moves the assets of a path swap priced by its pools. Each hop is paid directly by the
+        // previous pool, so only the first and last assets pass through the user's account.
+        fn settle_path_swap(
+            user: &AccountIdOf<T>,
+            path: &[AssetKindOf<T>],
+            pools: &[(AssetPair<T>, LiquidityPool<T>)],
+            amounts: &[AssetBalanceOf<T>],
+        ) -> DispatchResult {
+            let mut payer = user.clone();
+            for (hop, (trading_pair, _)) in pools.iter().enumerate() {
+                let pool_account = Self::pool_account_id(trading_pair);
+                Self::transfer_asset(path[hop], &payer, &pool_account, amounts[hop])?;
+                payer = pool_account;
+            }
+
+            let last = pools.len();
+            Self::transfer_asset(path[last], &payer, user, amounts[last])
+        }
+
+        fn transfer_asset(
+            asset_id: AssetKindOf<T>,
+            from: &AccountIdOf<T>,
+            to: &AccountIdOf<T>,
+            amount: AssetBalanceOf<T>,
+        ) -> DispatchResult {
+            NativeOrAssets::<T>::transfer(asset_id, from, to, amount, Preservation::Expendable)?;
+            Ok(())
+        }
+
         fn transfer_asset_to_user(
+            trading_pair: &AssetPair<T>,
             user: &AccountIdOf<T>,
-            asset_id: AssetIdOf<T>,
+            asset_id: AssetKindOf<T>,
             amount: AssetBalanceOf<T>,
         ) -> DispatchResult {
-            T::Fungibles::transfer(
+            NativeOrAssets::<T>::transfer(
                 asset_id,
-                &Self::pallet_account_id(),
+                &Self::pool_account_id(trading_pair),
                 user,
                 amount,
                 Preservation::Expendable,
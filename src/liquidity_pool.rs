@@ -1,17 +1,27 @@
 use super::*;
+use crate::concentrated_liquidity;
+use crate::stable_swap;
+use crate::traits::{AmmCurve, ConstantProduct};
+use crate::weighted_math;
 use frame_support::pallet_prelude::*;
-use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Zero};
-use sp_runtime::Permill;
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::helpers_128bit::multiply_by_rational_with_rounding;
+use sp_runtime::traits::{
+    AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One,
+    SaturatedConversion, Saturating, Zero,
+};
+use sp_runtime::{BoundedBTreeMap, FixedPointNumber, FixedU128, Permill, Rounding};
+use sp_std::vec::Vec;
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct AssetPair<T: Config> {
-    pub asset_a: AssetIdOf<T>,
-    pub asset_b: AssetIdOf<T>,
+    pub asset_a: AssetKindOf<T>,
+    pub asset_b: AssetKindOf<T>,
 }
 
 impl<T: Config> AssetPair<T> {
-    pub fn new(asset_one: AssetIdOf<T>, asset_two: AssetIdOf<T>) -> Self {
+    pub fn new(asset_one: AssetKindOf<T>, asset_two: AssetKindOf<T>) -> Self {
         if asset_one <= asset_two {
             AssetPair {
                 asset_a: asset_one,
@@ -27,6 +37,30 @@ impl<T: Config> AssetPair<T> {
     }
 }
 
+/// Difference between what a pool account holds and the reserves recorded for the pool.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct ReserveDrift<Balance> {
+    /// Amounts of asset A and asset B held beyond the reserves.
+    pub excess: (Balance, Balance),
+    /// Amounts of asset A and asset B missing from the holdings.
+    pub shortfall: (Balance, Balance),
+}
+
+impl<Balance: Copy + Saturating> ReserveDrift<Balance> {
+    pub fn new(holdings: (Balance, Balance), reserves: (Balance, Balance)) -> Self {
+        ReserveDrift {
+            excess: (
+                holdings.0.saturating_sub(reserves.0),
+                holdings.1.saturating_sub(reserves.1),
+            ),
+            shortfall: (
+                reserves.0.saturating_sub(holdings.0),
+                reserves.1.saturating_sub(holdings.1),
+            ),
+        }
+    }
+}
+
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct LiquidityPool<T: Config> {
@@ -34,9 +68,342 @@ pub struct LiquidityPool<T: Config> {
     pub reserves: (AssetBalanceOf<T>, AssetBalanceOf<T>),
     pub total_liquidity: AssetBalanceOf<T>,
     pub liquidity_token: AssetIdOf<T>,
+    pub swap_fee: Permill,
+    pub protocol_fee: Permill,
+    /// The protocol fees withheld from swaps, held by the pool account apart from the reserves
+    /// until they are claimed for the protocol fee receiver
+    pub protocol_fees: (AssetBalanceOf<T>, AssetBalanceOf<T>),
+    /// The account that created the pool and whose deposit is held for it
+    pub depositor: AccountIdOf<T>,
+    /// The amount of the native currency held from the depositor
+    pub deposit: BalanceOf<T>,
+    /// Sums of the price of asset A in asset B and of asset B in asset A, as the inner values of
+    /// `FixedU128`, weighted by the blocks each price was held for. They wrap on overflow, only
+    /// differences between them are meaningful.
+    pub price_cumulative: (u128, u128),
+    /// The block the cumulative prices were last updated in
+    pub last_update: BlockNumberFor<T>,
+    /// The operations currently allowed on the pool
+    pub status: PoolStatus,
+    /// The invariant the pool prices swaps and deposits with
+    pub kind: PoolKind<BlockNumberFor<T>, T::Curve>,
+}
+
+/// The invariant a pool prices swaps and deposits with.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum PoolKind<BlockNumber, Curve> {
+    /// One of the curves supported by the runtime through `Config::Curve`.
+    Curve(Curve),
+    /// The StableSwap invariant for assets expected to trade close to one to one.
+    StableSwap(Amplification<BlockNumber>),
+    /// The product of the reserves each raised to its weight is kept constant, the weights of
+    /// asset A and asset B sum to one.
+    Weighted {
+        weight_a: Permill,
+        weight_b: Permill,
+    },
+    /// Liquidity provided by positions between two ticks, each only backing swaps while the
+    /// price is within its range.
+    Concentrated(ConcentratedLiquidity),
+}
+
+/// The lowest weight an asset of a weighted pool can have
+pub const MIN_WEIGHT: Permill = Permill::from_percent(1);
+
+/// The largest factor a single ramp can change the amplification coefficient of a pool by
+pub const MAX_AMPLIFICATION_CHANGE: u32 = 10;
+
+/// The amplification coefficient of a StableSwap pool, moving linearly from `initial` at
+/// `initial_block` to `future` at `future_block`.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Amplification<BlockNumber> {
+    pub initial: u32,
+    pub future: u32,
+    pub initial_block: BlockNumber,
+    pub future_block: BlockNumber,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> Amplification<BlockNumber> {
+    // Function to create an amplification coefficient that does not change
+    pub fn constant(amplification: u32, now: BlockNumber) -> Self {
+        Amplification {
+            initial: amplification,
+            future: amplification,
+            initial_block: now,
+            future_block: now,
+        }
+    }
+
+    // Function to get the amplification coefficient at block `now`
+    pub fn at(&self, now: BlockNumber) -> u32 {
+        if now >= self.future_block {
+            return self.future;
+        }
+        if now <= self.initial_block {
+            return self.initial;
+        }
+
+        let elapsed: u128 = (now - self.initial_block).saturated_into();
+        let duration: u128 = (self.future_block - self.initial_block).saturated_into();
+        let (initial, future) = (self.initial as u128, self.future as u128);
+        let amplification = if future >= initial {
+            initial + (future - initial) * elapsed / duration
+        } else {
+            initial - (initial - future) * elapsed / duration
+        };
+        amplification.saturated_into()
+    }
+}
+
+/// The state of a concentrated liquidity pool, prices are of asset A in asset B.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct ConcentratedLiquidity {
+    /// The square root of the price as a Q64.64 fixed point number
+    pub sqrt_price: u128,
+    /// The highest tick whose square root price is at most `sqrt_price`
+    pub tick: i32,
+    /// The liquidity of the positions whose range contains the price
+    pub liquidity: u128,
+    /// The ticks bounding positions must be multiples of the spacing
+    pub tick_spacing: u32,
+    /// The swap fees earned per unit of liquidity in asset A and asset B since the pool was
+    /// created, as wrapping Q64.64 numbers
+    pub fee_growth_global: (u128, u128),
+}
+
+/// A tick bounding at least one position of a concentrated liquidity pool.
+#[derive(
+    Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub struct TickInfo {
+    /// The liquidity of the positions bounded by the tick
+    pub liquidity_gross: u128,
+    /// The liquidity that becomes active when the price crosses the tick upwards, and inactive
+    /// when it crosses downwards
+    pub liquidity_net: i128,
+    /// The fee growth on the other side of the tick from the price
+    pub fee_growth_outside: (u128, u128),
+}
+
+/// The initialized ticks of a concentrated liquidity pool.
+pub type TickMap<T> = BoundedBTreeMap<i32, TickInfo, <T as Config>::MaxInitializedTicks>;
+
+/// Liquidity an account provides between two ticks of a concentrated liquidity pool.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct Position<T: Config> {
+    pub liquidity: u128,
+    /// The fee growth inside the range of the position as of its last update
+    pub fee_growth_inside_last: (u128, u128),
+    /// The fees earned and the amounts withdrawn that are yet to be collected
+    pub tokens_owed: (AssetBalanceOf<T>, AssetBalanceOf<T>),
+}
+
+impl<T: Config> Default for Position<T> {
+    fn default() -> Self {
+        Position {
+            liquidity: 0,
+            fee_growth_inside_last: (0, 0),
+            tokens_owed: (Zero::zero(), Zero::zero()),
+        }
+    }
+}
+
+/// The operations allowed on a pool, or on all pools when set globally.
+#[derive(
+    Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub enum PoolStatus {
+    /// Swaps, deposits and withdrawals are allowed.
+    #[default]
+    Active,
+    /// Deposits and withdrawals are allowed, swaps are not.
+    SwapsPaused,
+    /// Nothing is allowed.
+    FullyPaused,
+    /// Only withdrawals are allowed.
+    WithdrawOnly,
+}
+
+impl PoolStatus {
+    pub fn allows_swaps(&self) -> bool {
+        matches!(self, PoolStatus::Active)
+    }
+
+    pub fn allows_minting(&self) -> bool {
+        matches!(self, PoolStatus::Active | PoolStatus::SwapsPaused)
+    }
+
+    pub fn allows_burning(&self) -> bool {
+        !matches!(self, PoolStatus::FullyPaused)
+    }
+}
+
+/// The cumulative prices of a pool as of a block.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct PriceObservation<T: Config> {
+    pub block_number: BlockNumberFor<T>,
+    pub price_cumulative: (u128, u128),
+}
+
+/// Ring buffer of the latest `Config::MaxPriceObservations` observations of a pool, overwriting
+/// the oldest one once full.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ObservationBuffer<T: Config> {
+    observations: BoundedVec<PriceObservation<T>, T::MaxPriceObservations>,
+    /// The position of the oldest observation once the buffer is full
+    next: u32,
+}
+
+impl<T: Config> Default for ObservationBuffer<T> {
+    fn default() -> Self {
+        ObservationBuffer {
+            observations: BoundedVec::default(),
+            next: 0,
+        }
+    }
+}
+
+impl<T: Config> ObservationBuffer<T> {
+    // Function to record an observation, overwriting the oldest one once the buffer is full
+    pub fn push(&mut self, observation: PriceObservation<T>) {
+        if let Err(observation) = self.observations.try_push(observation) {
+            let len = self.observations.len();
+            if len == 0 {
+                return;
+            }
+            let next = self.next as usize % len;
+            self.observations[next] = observation;
+            self.next = ((next + 1) % len) as u32;
+        }
+    }
+
+    // Function to iterate over the observations from the oldest to the latest
+    pub fn ordered(&self) -> impl Iterator<Item = &PriceObservation<T>> {
+        let (latest, oldest) = self
+            .observations
+            .split_at(self.next as usize % self.observations.len().max(1));
+        oldest.iter().chain(latest.iter())
+    }
 }
 
 impl<T: Config> LiquidityPool<T> {
+    // Function to accumulate the prices held since the last update, to be called on the first
+    // touch of the pool in a block before its reserves change. Returns whether the cumulative
+    // prices moved to `now`.
+    pub fn update_price_cumulative(&mut self, now: BlockNumberFor<T>) -> bool {
+        if now <= self.last_update {
+            return false;
+        }
+
+        let elapsed: u128 = now.saturating_sub(self.last_update).saturated_into();
+        if let Some((price_a, price_b)) = self.spot_prices(self.last_update) {
+            self.price_cumulative = (
+                self.price_cumulative
+                    .0
+                    .wrapping_add(price_a.wrapping_mul(elapsed)),
+                self.price_cumulative
+                    .1
+                    .wrapping_add(price_b.wrapping_mul(elapsed)),
+            );
+        }
+        self.last_update = now;
+        true
+    }
+
+    // Function to get the cumulative prices as of `now`, without updating the pool
+    pub fn price_cumulative_at(&self, now: BlockNumberFor<T>) -> (u128, u128) {
+        let mut liquidity_pool = self.clone();
+        liquidity_pool.update_price_cumulative(now);
+        liquidity_pool.price_cumulative
+    }
+
+    // Helper function to get the marginal price of asset A in asset B and of asset B in asset A
+    // at block `at` as the inner values of `FixedU128`, `None` while a reserve is empty unless
+    // the pool tracks its price apart from the reserves
+    fn spot_prices(&self, at: BlockNumberFor<T>) -> Option<(u128, u128)> {
+        let reserve_a: u128 = self.reserves.0.saturated_into();
+        let reserve_b: u128 = self.reserves.1.saturated_into();
+        let concentrated = matches!(self.kind, PoolKind::Concentrated(_));
+        if !concentrated && (reserve_a.is_zero() || reserve_b.is_zero()) {
+            return None;
+        }
+
+        match self.kind {
+            PoolKind::Curve(curve) => Some((
+                curve
+                    .spot_price(self.reserves.0, self.reserves.1)
+                    .into_inner(),
+                curve
+                    .spot_price(self.reserves.1, self.reserves.0)
+                    .into_inner(),
+            )),
+            PoolKind::StableSwap(amplification) => {
+                let amplification = amplification.at(at);
+                Some((
+                    stable_swap::spot_price((reserve_a, reserve_b), amplification)?,
+                    stable_swap::spot_price((reserve_b, reserve_a), amplification)?,
+                ))
+            }
+            PoolKind::Weighted { weight_a, weight_b } => {
+                // The ratio of the reserves, each divided by its weight
+                let (weight_a, weight_b) = (weight_a.deconstruct(), weight_b.deconstruct());
+                Some((
+                    FixedU128::saturating_from_rational(reserve_b, reserve_a)
+                        .saturating_mul(FixedU128::saturating_from_rational(weight_a, weight_b))
+                        .into_inner(),
+                    FixedU128::saturating_from_rational(reserve_a, reserve_b)
+                        .saturating_mul(FixedU128::saturating_from_rational(weight_b, weight_a))
+                        .into_inner(),
+                ))
+            }
+            PoolKind::Concentrated(state) => Some(concentrated_liquidity::prices(state.sqrt_price)),
+        }
+    }
+
+    // Function to get the invariant of the given reserves, which is the liquidity minted by the
+    // first deposit into the pool
+    pub fn invariant(
+        &self,
+        reserves: (AssetBalanceOf<T>, AssetBalanceOf<T>),
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        match self.kind {
+            // The shares the curve mints for depositing the reserves into an empty pool
+            PoolKind::Curve(curve) => {
+                curve.mint_shares(reserves, (Zero::zero(), Zero::zero()), Zero::zero())
+            }
+            PoolKind::StableSwap(amplification) => {
+                let d = stable_swap::compute_d(
+                    (reserves.0.saturated_into(), reserves.1.saturated_into()),
+                    amplification.at(frame_system::Pallet::<T>::block_number()),
+                )
+                .ok_or(Error::<T>::InvariantNotConverged)?;
+                Self::from_u128(d)
+            }
+            PoolKind::Weighted { weight_b, .. } => {
+                // reserve_a^weight_a·reserve_b^weight_b, written as
+                // reserve_a·(reserve_b / reserve_a)^weight_b as the weights sum to one
+                let reserve_a: u128 = reserves.0.saturated_into();
+                let ratio = FixedU128::checked_from_rational(
+                    reserves.1.saturated_into::<u128>(),
+                    reserve_a,
+                )
+                .ok_or(Error::<T>::ArithmeticOverflow)?;
+                let exponent = FixedU128::saturating_from_rational(
+                    weight_b.deconstruct(),
+                    Permill::one().deconstruct(),
+                );
+                let power = weighted_math::pow_down(ratio, exponent)
+                    .ok_or(Error::<T>::ArithmeticOverflow)?;
+                Self::mul_fixed(reserve_a, power, Rounding::Down)
+            }
+            // Positions hold liquidity rather than liquidity tokens
+            PoolKind::Concentrated(_) => Err(Error::<T>::ConcentratedLiquidityPool.into()),
+        }
+    }
+
     // Function to mint liquidity tokens and update reserves
     pub fn mint(
         &mut self,
@@ -83,40 +450,511 @@ impl<T: Config> LiquidityPool<T> {
         Ok(())
     }
 
-    // Function to swap tokens based on pool reserves
+    // Function to swap tokens based on pool reserves, returns the amount out. The part of
+    // `amount_in` owed to the protocol is accrued apart from the reserves.
     pub fn swap(
         &mut self,
-        asset_in: AssetIdOf<T>,
+        asset_in: AssetKindOf<T>,
         amount_in: AssetBalanceOf<T>,
-        asset_out: AssetIdOf<T>,
+        asset_out: AssetKindOf<T>,
         min_amount_out: AssetBalanceOf<T>,
     ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        let (reserve_in, reserve_out) = self.reserves_for(asset_in, asset_out)?;
+
+        let amount_out = self.get_amount_out(asset_in, amount_in, reserve_in, reserve_out)?;
         ensure!(
-            self.assets.asset_a == asset_in || self.assets.asset_b == asset_in,
-            Error::<T>::InvalidAssetIn
+            amount_out >= min_amount_out,
+            Error::<T>::InsufficientAmountOut
         );
+
+        self.update_reserves(asset_in, amount_in, amount_out)?;
+        Ok(amount_out)
+    }
+
+    // Function to swap tokens for an exact amount out, returns the amount in. The part of it
+    // owed to the protocol is accrued apart from the reserves.
+    pub fn swap_exact_out(
+        &mut self,
+        asset_in: AssetKindOf<T>,
+        amount_out: AssetBalanceOf<T>,
+        asset_out: AssetKindOf<T>,
+        max_amount_in: AssetBalanceOf<T>,
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        let (reserve_in, reserve_out) = self.reserves_for(asset_in, asset_out)?;
+
+        let amount_in = self.get_amount_in(asset_in, amount_out, reserve_in, reserve_out)?;
+        ensure!(amount_in <= max_amount_in, Error::<T>::ExcessiveAmountIn);
+
+        self.update_reserves(asset_in, amount_in, amount_out)?;
+        Ok(amount_in)
+    }
+
+    // Function to get the state of a concentrated liquidity pool
+    pub fn concentrated(&self) -> Result<ConcentratedLiquidity, DispatchError> {
+        match self.kind {
+            PoolKind::Concentrated(state) => Ok(state),
+            _ => Err(Error::<T>::NotConcentratedLiquidityPool.into()),
+        }
+    }
+
+    // Function to add `liquidity_delta` to a position between two ticks of a concentrated
+    // liquidity pool, or remove it when negative, crediting the fees the position earned since
+    // its last update. Returns the amounts of asset A and asset B backing the liquidity, which
+    // are added to the reserves when adding and owed to the position when removing.
+    pub fn modify_position(
+        &mut self,
+        ticks: &mut TickMap<T>,
+        position: &mut Position<T>,
+        (tick_lower, tick_upper): (i32, i32),
+        liquidity_delta: i128,
+    ) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+        let mut state = self.concentrated()?;
+        let tick_spacing = state.tick_spacing as i32;
         ensure!(
-            self.assets.asset_a == asset_out || self.assets.asset_b == asset_out,
-            Error::<T>::InvalidAssetOut
+            tick_lower < tick_upper
+                && tick_lower >= concentrated_liquidity::MIN_TICK
+                && tick_upper <= concentrated_liquidity::MAX_TICK
+                && tick_lower % tick_spacing == 0
+                && tick_upper % tick_spacing == 0,
+            Error::<T>::InvalidTickRange
+        );
+        let liquidity = Self::add_liquidity_delta(position.liquidity, liquidity_delta)?;
+
+        Self::update_tick(ticks, &state, tick_lower, liquidity_delta, false)?;
+        Self::update_tick(ticks, &state, tick_upper, liquidity_delta, true)?;
+
+        // Credit the fees earned by the liquidity the position held so far
+        let fee_growth_inside = Self::fee_growth_inside(ticks, &state, (tick_lower, tick_upper));
+        let fees = (
+            Self::from_u128(concentrated_liquidity::fees_earned(
+                fee_growth_inside.0,
+                position.fee_growth_inside_last.0,
+                position.liquidity,
+            ))?,
+            Self::from_u128(concentrated_liquidity::fees_earned(
+                fee_growth_inside.1,
+                position.fee_growth_inside_last.1,
+                position.liquidity,
+            ))?,
         );
+        position.liquidity = liquidity;
+        position.fee_growth_inside_last = fee_growth_inside;
 
-        let (reserve_in, reserve_out) = if self.assets.asset_a == asset_in {
-            (self.reserves.0, self.reserves.1)
+        // Ticks no longer bounding any position are cleared
+        for tick in [tick_lower, tick_upper] {
+            if matches!(ticks.get(&tick), Some(info) if info.liquidity_gross == 0) {
+                ticks.remove(&tick);
+            }
+        }
+
+        // The amounts backing the liquidity depend on where the price is relative to the range,
+        // rounded up when adding and down when removing so that rounding favours the pool
+        let round_up = liquidity_delta > 0;
+        let magnitude = liquidity_delta.unsigned_abs();
+        let sqrt_price_lower = concentrated_liquidity::sqrt_price_at_tick(tick_lower)
+            .ok_or(Error::<T>::InvalidTickRange)?;
+        let sqrt_price_upper = concentrated_liquidity::sqrt_price_at_tick(tick_upper)
+            .ok_or(Error::<T>::InvalidTickRange)?;
+        let (amount_a, amount_b) = if state.tick < tick_lower {
+            (
+                concentrated_liquidity::amount_a_delta(
+                    sqrt_price_lower,
+                    sqrt_price_upper,
+                    magnitude,
+                    round_up,
+                ),
+                Some(0),
+            )
+        } else if state.tick < tick_upper {
+            state.liquidity = Self::add_liquidity_delta(state.liquidity, liquidity_delta)?;
+            (
+                concentrated_liquidity::amount_a_delta(
+                    state.sqrt_price,
+                    sqrt_price_upper,
+                    magnitude,
+                    round_up,
+                ),
+                concentrated_liquidity::amount_b_delta(
+                    sqrt_price_lower,
+                    state.sqrt_price,
+                    magnitude,
+                    round_up,
+                ),
+            )
         } else {
-            (self.reserves.1, self.reserves.0)
+            (
+                Some(0),
+                concentrated_liquidity::amount_b_delta(
+                    sqrt_price_lower,
+                    sqrt_price_upper,
+                    magnitude,
+                    round_up,
+                ),
+            )
         };
+        let amounts = (
+            Self::from_u128(amount_a.ok_or(Error::<T>::ArithmeticOverflow)?)?,
+            Self::from_u128(amount_b.ok_or(Error::<T>::ArithmeticOverflow)?)?,
+        );
 
-        let amount_out = Self::get_amount_out(amount_in, reserve_in, reserve_out)?;
+        let owed = if liquidity_delta > 0 {
+            self.mint(amounts, Zero::zero())?;
+            fees
+        } else {
+            (
+                fees.0
+                    .checked_add(&amounts.0)
+                    .ok_or(Error::<T>::ArithmeticOverflow)?,
+                fees.1
+                    .checked_add(&amounts.1)
+                    .ok_or(Error::<T>::ArithmeticOverflow)?,
+            )
+        };
+        position.tokens_owed = (
+            position
+                .tokens_owed
+                .0
+                .checked_add(&owed.0)
+                .ok_or(Error::<T>::ArithmeticOverflow)?,
+            position
+                .tokens_owed
+                .1
+                .checked_add(&owed.1)
+                .ok_or(Error::<T>::ArithmeticOverflow)?,
+        );
+
+        self.kind = PoolKind::Concentrated(state);
+        Ok(amounts)
+    }
+
+    // Function to swap an exact amount in against a concentrated liquidity pool, crossing the
+    // initialized ticks on the way. Returns the amount out, the part of `amount_in` owed to the
+    // protocol is accrued apart from the reserves.
+    pub fn swap_concentrated(
+        &mut self,
+        ticks: &mut TickMap<T>,
+        asset_in: AssetKindOf<T>,
+        amount_in: AssetBalanceOf<T>,
+        asset_out: AssetKindOf<T>,
+        min_amount_out: AssetBalanceOf<T>,
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        let (_, amount_out) =
+            self.swap_across_ticks(ticks, asset_in, asset_out, amount_in, true)?;
         ensure!(
             amount_out >= min_amount_out,
             Error::<T>::InsufficientAmountOut
         );
+        Ok(amount_out)
+    }
+
+    // Function to swap against a concentrated liquidity pool for an exact amount out, crossing
+    // the initialized ticks on the way. Returns the amount in, the part of it owed to the
+    // protocol is accrued apart from the reserves.
+    pub fn swap_concentrated_exact_out(
+        &mut self,
+        ticks: &mut TickMap<T>,
+        asset_in: AssetKindOf<T>,
+        amount_out: AssetBalanceOf<T>,
+        asset_out: AssetKindOf<T>,
+        max_amount_in: AssetBalanceOf<T>,
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        let (amount_in, _) =
+            self.swap_across_ticks(ticks, asset_in, asset_out, amount_out, false)?;
+        ensure!(amount_in <= max_amount_in, Error::<T>::ExcessiveAmountIn);
+        Ok(amount_in)
+    }
+
+    // Helper function to swap against a concentrated liquidity pool step by step until `amount`
+    // is used up, the amount in with the fee when `exact_in` and the amount out otherwise.
+    // Returns the amounts in and out and updates the reserves.
+    fn swap_across_ticks(
+        &mut self,
+        ticks: &mut TickMap<T>,
+        asset_in: AssetKindOf<T>,
+        asset_out: AssetKindOf<T>,
+        amount: AssetBalanceOf<T>,
+        exact_in: bool,
+    ) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+        let mut state = self.concentrated()?;
+        self.reserves_for(asset_in, asset_out)?;
+
+        // Selling asset A moves the price down, selling asset B moves it up
+        let a_to_b = self.assets.asset_a == asset_in;
+        let boundary = if a_to_b {
+            concentrated_liquidity::MIN_TICK
+        } else {
+            concentrated_liquidity::MAX_TICK
+        };
+        let sqrt_price_limit = concentrated_liquidity::sqrt_price_at_tick(boundary)
+            .ok_or(Error::<T>::ArithmeticOverflow)?;
+
+        let mut amount_remaining: u128 = amount.saturated_into();
+        let mut amount_in: u128 = 0;
+        let mut amount_out: u128 = 0;
+        let mut protocol_fee: u128 = 0;
+        // Every step but the last crosses an initialized tick or reaches the end of the range
+        let max_steps = T::MaxInitializedTicks::get().saturating_add(1);
+        let mut steps: u32 = 0;
+        while !amount_remaining.is_zero() {
+            ensure!(steps < max_steps, Error::<T>::TooManySwapSteps);
+            steps += 1;
+
+            // Nothing is left to swap against past the end of the tick range
+            ensure!(
+                state.sqrt_price != sqrt_price_limit,
+                Error::<T>::InsufficientLiquidity
+            );
+
+            // Swap up to the next initialized tick in the direction of the swap
+            let next_tick = if a_to_b {
+                ticks.range(..=state.tick).next_back()
+            } else {
+                ticks.range(state.tick.saturating_add(1)..).next()
+            }
+            .map_or(boundary, |(tick, _)| *tick);
+            let sqrt_price_target = concentrated_liquidity::sqrt_price_at_tick(next_tick)
+                .ok_or(Error::<T>::ArithmeticOverflow)?;
+            let compute_swap_step = if exact_in {
+                concentrated_liquidity::compute_swap_step
+            } else {
+                concentrated_liquidity::compute_swap_step_exact_out
+            };
+            let step = compute_swap_step(
+                state.sqrt_price,
+                sqrt_price_target,
+                state.liquidity,
+                amount_remaining,
+                self.swap_fee.deconstruct(),
+            )
+            .ok_or(Error::<T>::ArithmeticOverflow)?;
+
+            let step_amount_in = step.amount_in.saturating_add(step.fee);
+            amount_remaining = amount_remaining
+                .checked_sub(if exact_in {
+                    step_amount_in
+                } else {
+                    step.amount_out
+                })
+                .ok_or(Error::<T>::ArithmeticOverflow)?;
+            amount_in = amount_in
+                .checked_add(step_amount_in)
+                .ok_or(Error::<T>::ArithmeticOverflow)?;
+            amount_out = amount_out
+                .checked_add(step.amount_out)
+                .ok_or(Error::<T>::ArithmeticOverflow)?;
+
+            // The protocol's share of the fee is set aside, the rest accrues to the liquidity in
+            // range
+            let step_protocol_fee = self.protocol_fee.mul_floor(step.fee);
+            protocol_fee = protocol_fee.saturating_add(step_protocol_fee);
+            let fee_growth = if a_to_b {
+                &mut state.fee_growth_global.0
+            } else {
+                &mut state.fee_growth_global.1
+            };
+            *fee_growth = concentrated_liquidity::accumulate_fee_growth(
+                *fee_growth,
+                step.fee - step_protocol_fee,
+                state.liquidity,
+            );
+
+            state.sqrt_price = step.sqrt_price;
+            if step.sqrt_price == sqrt_price_target {
+                // Crossing a tick flips its fee growth outside to the other side and changes the
+                // liquidity in range
+                if let Some(info) = ticks.get_mut(&next_tick) {
+                    info.fee_growth_outside = (
+                        state
+                            .fee_growth_global
+                            .0
+                            .wrapping_sub(info.fee_growth_outside.0),
+                        state
+                            .fee_growth_global
+                            .1
+                            .wrapping_sub(info.fee_growth_outside.1),
+                    );
+                    let liquidity_net = if a_to_b {
+                        info.liquidity_net.saturating_neg()
+                    } else {
+                        info.liquidity_net
+                    };
+                    state.liquidity = Self::add_liquidity_delta(state.liquidity, liquidity_net)?;
+                }
+                state.tick = if a_to_b { next_tick - 1 } else { next_tick };
+            } else {
+                state.tick = concentrated_liquidity::tick_at_sqrt_price(state.sqrt_price)
+                    .ok_or(Error::<T>::ArithmeticOverflow)?;
+            }
+        }
+
+        let amount_in = Self::from_u128(amount_in)?;
+        let amount_out = Self::from_u128(amount_out)?;
+        let protocol_fee = Self::from_u128(protocol_fee)?;
+        let reserve_in_added = amount_in
+            .checked_sub(&protocol_fee)
+            .ok_or(Error::<T>::ArithmeticOverflow)?;
+        self.move_reserves(asset_in, reserve_in_added, amount_out)?;
+        self.accrue_protocol_fee(asset_in, protocol_fee)?;
+        self.kind = PoolKind::Concentrated(state);
+        Ok((amount_in, amount_out))
+    }
+
+    // Helper function to add liquidity to a tick bounding a position from below, or from above
+    // when `upper`, initializing the tick as needed
+    fn update_tick(
+        ticks: &mut TickMap<T>,
+        state: &ConcentratedLiquidity,
+        tick: i32,
+        liquidity_delta: i128,
+        upper: bool,
+    ) -> DispatchResult {
+        let mut info = match ticks.get(&tick) {
+            Some(info) => *info,
+            // All fee growth so far is taken to be below a new tick at or below the price
+            None => TickInfo {
+                fee_growth_outside: if tick <= state.tick {
+                    state.fee_growth_global
+                } else {
+                    (0, 0)
+                },
+                ..Default::default()
+            },
+        };
+        info.liquidity_gross = Self::add_liquidity_delta(info.liquidity_gross, liquidity_delta)?;
+        // The liquidity of the position becomes active above its lower tick and inactive above
+        // its upper tick
+        let liquidity_net = if upper {
+            info.liquidity_net.checked_sub(liquidity_delta)
+        } else {
+            info.liquidity_net.checked_add(liquidity_delta)
+        };
+        info.liquidity_net = liquidity_net.ok_or(Error::<T>::LiquidityOverflow)?;
+        ticks
+            .try_insert(tick, info)
+            .map_err(|_| Error::<T>::TooManyTicks)?;
+        Ok(())
+    }
+
+    // Helper function to compute the fee growth between two initialized ticks
+    fn fee_growth_inside(
+        ticks: &TickMap<T>,
+        state: &ConcentratedLiquidity,
+        (tick_lower, tick_upper): (i32, i32),
+    ) -> (u128, u128) {
+        let outside = |tick: i32| {
+            ticks
+                .get(&tick)
+                .map(|info| info.fee_growth_outside)
+                .unwrap_or_default()
+        };
+        let global = state.fee_growth_global;
+        let complement = |growth: (u128, u128)| {
+            (
+                global.0.wrapping_sub(growth.0),
+                global.1.wrapping_sub(growth.1),
+            )
+        };
+
+        let below = if state.tick >= tick_lower {
+            outside(tick_lower)
+        } else {
+            complement(outside(tick_lower))
+        };
+        let above = if state.tick < tick_upper {
+            outside(tick_upper)
+        } else {
+            complement(outside(tick_upper))
+        };
+        (
+            global.0.wrapping_sub(below.0).wrapping_sub(above.0),
+            global.1.wrapping_sub(below.1).wrapping_sub(above.1),
+        )
+    }
+
+    // Helper function to add a signed amount of liquidity
+    fn add_liquidity_delta(liquidity: u128, liquidity_delta: i128) -> Result<u128, DispatchError> {
+        let liquidity = if liquidity_delta >= 0 {
+            liquidity
+                .checked_add(liquidity_delta.unsigned_abs())
+                .ok_or(Error::<T>::LiquidityOverflow)?
+        } else {
+            liquidity
+                .checked_sub(liquidity_delta.unsigned_abs())
+                .ok_or(Error::<T>::InsufficientLiquidity)?
+        };
+        Ok(liquidity)
+    }
+
+    // Helper function to get the (reserve in, reserve out) of a swap direction
+    fn reserves_for(
+        &self,
+        asset_in: AssetKindOf<T>,
+        asset_out: AssetKindOf<T>,
+    ) -> Result<(AssetBalanceOf<T>, AssetBalanceOf<T>), DispatchError> {
+        ensure!(
+            self.assets.asset_a == asset_in || self.assets.asset_b == asset_in,
+            Error::<T>::InvalidAssetIn
+        );
+        ensure!(
+            asset_out != asset_in
+                && (self.assets.asset_a == asset_out || self.assets.asset_b == asset_out),
+            Error::<T>::InvalidAssetOut
+        );
 
+        if self.assets.asset_a == asset_in {
+            Ok((self.reserves.0, self.reserves.1))
+        } else {
+            Ok((self.reserves.1, self.reserves.0))
+        }
+    }
+
+    // Helper function to move a swap's amounts into and out of the reserves, setting the
+    // protocol fee withheld from `amount_in` aside
+    fn update_reserves(
+        &mut self,
+        asset_in: AssetKindOf<T>,
+        amount_in: AssetBalanceOf<T>,
+        amount_out: AssetBalanceOf<T>,
+    ) -> DispatchResult {
+        let protocol_fee = self.protocol_fee_amount(amount_in);
+        let reserve_in_added = amount_in
+            .checked_sub(&protocol_fee)
+            .ok_or(Error::<T>::ArithmeticOverflow)?;
+
+        self.move_reserves(asset_in, reserve_in_added, amount_out)?;
+        self.accrue_protocol_fee(asset_in, protocol_fee)
+    }
+
+    // Helper function to add a protocol fee paid in `asset` to the fees accrued by the pool
+    fn accrue_protocol_fee(
+        &mut self,
+        asset: AssetKindOf<T>,
+        protocol_fee: AssetBalanceOf<T>,
+    ) -> DispatchResult {
+        let accrued = if self.assets.asset_a == asset {
+            &mut self.protocol_fees.0
+        } else {
+            &mut self.protocol_fees.1
+        };
+        *accrued = accrued
+            .checked_add(&protocol_fee)
+            .ok_or(Error::<T>::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // Helper function to add `reserve_in_added` to the reserve of `asset_in` and take
+    // `amount_out` from the other one
+    fn move_reserves(
+        &mut self,
+        asset_in: AssetKindOf<T>,
+        reserve_in_added: AssetBalanceOf<T>,
+        amount_out: AssetBalanceOf<T>,
+    ) -> DispatchResult {
         if self.assets.asset_a == asset_in {
             self.reserves.0 = self
                 .reserves
                 .0
-                .checked_add(&amount_in)
+                .checked_add(&reserve_in_added)
                 .ok_or(Error::<T>::ReserveOverflow)?;
             self.reserves.1 = self
                 .reserves
@@ -132,15 +970,23 @@ impl<T: Config> LiquidityPool<T> {
             self.reserves.1 = self
                 .reserves
                 .1
-                .checked_add(&amount_in)
+                .checked_add(&reserve_in_added)
                 .ok_or(Error::<T>::ReserveOverflow)?;
         }
 
-        Ok(amount_out)
+        Ok(())
+    }
+
+    // Helper function to calculate the share of the swap fee on `amount_in` owed to the protocol
+    fn protocol_fee_amount(&self, amount_in: AssetBalanceOf<T>) -> AssetBalanceOf<T> {
+        self.protocol_fee
+            .mul_floor(self.swap_fee.mul_floor(amount_in))
     }
 
     // Helper function to calculate the amount of tokens to receive in a swap
     fn get_amount_out(
+        &self,
+        asset_in: AssetKindOf<T>,
         amount_in: AssetBalanceOf<T>,
         reserve_in: AssetBalanceOf<T>,
         reserve_out: AssetBalanceOf<T>,
@@ -151,30 +997,451 @@ impl<T: Config> LiquidityPool<T> {
             Error::<T>::InsufficientLiquidity
         );
 
-        // Define the swap fee as a Permill value (0.3%)
-        let swap_fee = Permill::from_perthousand(3);
-
-        // Calculate the input amount after deducting the swap fee
+        // Calculate the input amount after deducting the pool's swap fee
         let amount_in_after_fee = amount_in
-            .checked_sub(&swap_fee.mul_floor(amount_in))
+            .checked_sub(&self.swap_fee.mul_floor(amount_in))
             .ok_or(Error::<T>::ArithmeticOverflow)?;
 
-        // Calculate the numerator of the output amount formula
-        let numerator = amount_in_after_fee
-            .checked_mul(&reserve_out)
-            .ok_or(Error::<T>::ArithmeticOverflow)?;
+        match self.kind {
+            PoolKind::Curve(curve) => {
+                curve.amount_out(amount_in_after_fee, reserve_in, reserve_out)
+            }
+            PoolKind::StableSwap(amplification) => Self::stable_swap_amount_out(
+                amount_in_after_fee,
+                reserve_in,
+                reserve_out,
+                amplification.at(frame_system::Pallet::<T>::block_number()),
+            ),
+            PoolKind::Weighted { weight_a, weight_b } => Self::weighted_amount_out(
+                amount_in_after_fee,
+                reserve_in,
+                reserve_out,
+                self.weights_for(asset_in, (weight_a, weight_b)),
+            ),
+            // Swaps against concentrated liquidity go through `swap_concentrated`
+            PoolKind::Concentrated(_) => Err(Error::<T>::ConcentratedLiquidityPool.into()),
+        }
+    }
+
+    // Helper function to calculate the amount of tokens to pay for an exact amount out, rounded
+    // up so that the pool never receives less than the invariant of the pool requires
+    fn get_amount_in(
+        &self,
+        asset_in: AssetKindOf<T>,
+        amount_out: AssetBalanceOf<T>,
+        reserve_in: AssetBalanceOf<T>,
+        reserve_out: AssetBalanceOf<T>,
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        // Ensure that both reserve balances are non-zero
+        ensure!(
+            !reserve_in.is_zero() && !reserve_out.is_zero(),
+            Error::<T>::InsufficientLiquidity
+        );
+        // The pool can never be fully drained of the asset bought
+        ensure!(amount_out < reserve_out, Error::<T>::InsufficientReserves);
+
+        // Calculate the input amount the pool must receive after the swap fee is deducted
+        let amount_in_after_fee = match self.kind {
+            PoolKind::Curve(curve) => curve.amount_in(amount_out, reserve_in, reserve_out)?,
+            PoolKind::StableSwap(amplification) => Self::stable_swap_amount_in(
+                amount_out,
+                reserve_in,
+                reserve_out,
+                amplification.at(frame_system::Pallet::<T>::block_number()),
+            )?,
+            PoolKind::Weighted { weight_a, weight_b } => Self::weighted_amount_in(
+                amount_out,
+                reserve_in,
+                reserve_out,
+                self.weights_for(asset_in, (weight_a, weight_b)),
+            )?,
+            // Swaps against concentrated liquidity go through `swap_concentrated_exact_out`
+            PoolKind::Concentrated(_) => return Err(Error::<T>::ConcentratedLiquidityPool.into()),
+        };
+
+        // Gross the amount up by the pool's swap fee
+        let accuracy = Permill::one().deconstruct();
+        let parts_per_million = AssetBalanceOf::<T>::from(accuracy);
+        let parts_after_fee =
+            AssetBalanceOf::<T>::from(accuracy.saturating_sub(self.swap_fee.deconstruct()));
+        let amount_in = Self::div_ceil(
+            amount_in_after_fee
+                .checked_mul(&parts_per_million)
+                .ok_or(Error::<T>::ArithmeticOverflow)?,
+            parts_after_fee,
+        )?;
+
+        Ok(amount_in)
+    }
+
+    // Helper function to calculate the amount out of the StableSwap invariant for an amount in
+    // after fees, one unit less than the exact amount so that rounding favours the pool
+    fn stable_swap_amount_out(
+        amount_in_after_fee: AssetBalanceOf<T>,
+        reserve_in: AssetBalanceOf<T>,
+        reserve_out: AssetBalanceOf<T>,
+        amplification: u32,
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        let reserve_out: u128 = reserve_out.saturated_into();
+        let d = stable_swap::compute_d((reserve_in.saturated_into(), reserve_out), amplification)
+            .ok_or(Error::<T>::InvariantNotConverged)?;
 
-        // Calculate the denominator of the output amount formula
-        let denominator = reserve_in
+        let reserve_in_after: u128 = reserve_in
             .checked_add(&amount_in_after_fee)
+            .ok_or(Error::<T>::ArithmeticOverflow)?
+            .saturated_into();
+        let reserve_out_after = stable_swap::compute_y(reserve_in_after, d, amplification)
+            .ok_or(Error::<T>::InvariantNotConverged)?;
+
+        Self::from_u128(
+            reserve_out
+                .saturating_sub(reserve_out_after)
+                .saturating_sub(1),
+        )
+    }
+
+    // Helper function to calculate the amount in after fees the StableSwap invariant requires
+    // for an exact amount out, one unit more than the exact amount so that rounding favours the
+    // pool
+    fn stable_swap_amount_in(
+        amount_out: AssetBalanceOf<T>,
+        reserve_in: AssetBalanceOf<T>,
+        reserve_out: AssetBalanceOf<T>,
+        amplification: u32,
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        let reserve_in: u128 = reserve_in.saturated_into();
+        let d = stable_swap::compute_d((reserve_in, reserve_out.saturated_into()), amplification)
+            .ok_or(Error::<T>::InvariantNotConverged)?;
+
+        let reserve_out_after: u128 = reserve_out
+            .checked_sub(&amount_out)
+            .ok_or(Error::<T>::InsufficientReserves)?
+            .saturated_into();
+        let reserve_in_after = stable_swap::compute_y(reserve_out_after, d, amplification)
+            .ok_or(Error::<T>::InvariantNotConverged)?;
+
+        Self::from_u128(
+            reserve_in_after
+                .saturating_sub(reserve_in)
+                .saturating_add(1),
+        )
+    }
+
+    // Helper function to order the weights of asset A and asset B as (weight in, weight out)
+    fn weights_for(
+        &self,
+        asset_in: AssetKindOf<T>,
+        (weight_a, weight_b): (Permill, Permill),
+    ) -> (Permill, Permill) {
+        if self.assets.asset_a == asset_in {
+            (weight_a, weight_b)
+        } else {
+            (weight_b, weight_a)
+        }
+    }
+
+    // Helper function to calculate the amount out of the weighted constant mean for an amount in
+    // after fees, `reserve_out·(1 - (reserve_in / (reserve_in + amount_in))^(weight_in / weight_out))`
+    // with the power rounded up so that rounding favours the pool
+    fn weighted_amount_out(
+        amount_in_after_fee: AssetBalanceOf<T>,
+        reserve_in: AssetBalanceOf<T>,
+        reserve_out: AssetBalanceOf<T>,
+        (weight_in, weight_out): (Permill, Permill),
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        let reserve_in_after: u128 = reserve_in
+            .checked_add(&amount_in_after_fee)
+            .ok_or(Error::<T>::ArithmeticOverflow)?
+            .saturated_into();
+        let base =
+            FixedU128::checked_from_rational(reserve_in.saturated_into::<u128>(), reserve_in_after)
+                .ok_or(Error::<T>::DivisionByZero)?;
+        let exponent =
+            FixedU128::checked_from_rational(weight_in.deconstruct(), weight_out.deconstruct())
+                .ok_or(Error::<T>::DivisionByZero)?;
+        let power = weighted_math::pow_up(base, exponent).ok_or(Error::<T>::ArithmeticOverflow)?;
+
+        let share_out = FixedU128::one().saturating_sub(power);
+        Self::mul_fixed(reserve_out.saturated_into(), share_out, Rounding::Down)
+    }
+
+    // Helper function to calculate the amount in after fees the weighted constant mean requires
+    // for an exact amount out, `reserve_in·((reserve_out / (reserve_out - amount_out))^(weight_out
+    // / weight_in) - 1)` with the power rounded up so that rounding favours the pool
+    fn weighted_amount_in(
+        amount_out: AssetBalanceOf<T>,
+        reserve_in: AssetBalanceOf<T>,
+        reserve_out: AssetBalanceOf<T>,
+        (weight_in, weight_out): (Permill, Permill),
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        let reserve_out_after: u128 = reserve_out
+            .checked_sub(&amount_out)
+            .ok_or(Error::<T>::InsufficientReserves)?
+            .saturated_into();
+        let base = FixedU128::checked_from_rational(
+            reserve_out.saturated_into::<u128>(),
+            reserve_out_after,
+        )
+        .ok_or(Error::<T>::ArithmeticOverflow)?;
+        let exponent =
+            FixedU128::checked_from_rational(weight_out.deconstruct(), weight_in.deconstruct())
+                .ok_or(Error::<T>::DivisionByZero)?;
+        let power = weighted_math::pow_up(base, exponent).ok_or(Error::<T>::ArithmeticOverflow)?;
+
+        let share_in = power.saturating_sub(FixedU128::one());
+        Self::mul_fixed(reserve_in.saturated_into(), share_in, Rounding::Up)
+    }
+
+    // Helper function to multiply an amount by a fixed point number, rounding as given
+    fn mul_fixed(
+        amount: u128,
+        multiplier: FixedU128,
+        rounding: Rounding,
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        let product = multiply_by_rational_with_rounding(
+            amount,
+            multiplier.into_inner(),
+            FixedU128::DIV,
+            rounding,
+        )
+        .ok_or(Error::<T>::ArithmeticOverflow)?;
+        Self::from_u128(product)
+    }
+
+    // Helper function to convert the result of the invariant math back to a balance
+    fn from_u128(amount: u128) -> Result<AssetBalanceOf<T>, DispatchError> {
+        AssetBalanceOf::<T>::try_from(amount).map_err(|_| Error::<T>::ArithmeticOverflow.into())
+    }
+
+    // Helper function for integer division rounding up
+    fn div_ceil(
+        numerator: AssetBalanceOf<T>,
+        denominator: AssetBalanceOf<T>,
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        ensure!(!denominator.is_zero(), Error::<T>::DivisionByZero);
+        let quotient = numerator / denominator;
+        if (numerator % denominator).is_zero() {
+            return Ok(quotient);
+        }
+
+        let rounded_up = quotient
+            .checked_add(&One::one())
             .ok_or(Error::<T>::ArithmeticOverflow)?;
+        Ok(rounded_up)
+    }
+}
+
+/// The assets of a multi-asset pool, sorted and without duplicates, which key the pool.
+pub type PoolAssets<T> = BoundedVec<AssetKindOf<T>, <T as Config>::MaxAssetsPerPool>;
+
+/// An amount of each asset of a multi-asset pool, in the order of its assets.
+pub type PoolAmounts<T> = BoundedVec<AssetBalanceOf<T>, <T as Config>::MaxAssetsPerPool>;
+
+/// A pool of up to `Config::MaxAssetsPerPool` assets of equal weight, keeping the product of
+/// its reserves constant. A swap only moves the reserves of the two assets traded, so it is
+/// priced by their constant product.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct MultiAssetPool<T: Config> {
+    pub assets: PoolAssets<T>,
+    /// The reserve of each asset, in the order of `assets`
+    pub reserves: PoolAmounts<T>,
+    pub total_liquidity: AssetBalanceOf<T>,
+    pub liquidity_token: AssetIdOf<T>,
+    pub swap_fee: Permill,
+    pub protocol_fee: Permill,
+    /// The protocol fees withheld from swaps in the order of `assets`, held by the pool account
+    /// apart from the reserves until they are claimed for the protocol fee receiver
+    pub protocol_fees: PoolAmounts<T>,
+    /// The account that created the pool and whose deposit is held for it
+    pub depositor: AccountIdOf<T>,
+    /// The amount of the native currency held from the depositor
+    pub deposit: BalanceOf<T>,
+    /// The operations currently allowed on the pool
+    pub status: PoolStatus,
+}
 
-        // Perform integer division to obtain the final output amount
-        let amount_out = numerator
-            .checked_div(&denominator)
+impl<T: Config> MultiAssetPool<T> {
+    // Function to get the position of an asset in the pool, `None` if it is not a member
+    pub fn position(&self, asset: AssetKindOf<T>) -> Option<usize> {
+        self.assets.binary_search(&asset).ok()
+    }
+
+    // Function to get the geometric mean of the given amounts, which is the liquidity minted by
+    // the first deposit into the pool. It is computed as first·Π(amount / first)^(1/n) so that
+    // the fixed point numbers stay in range for large balances.
+    pub fn invariant(amounts: &[AssetBalanceOf<T>]) -> Result<AssetBalanceOf<T>, DispatchError> {
+        let first: u128 = amounts
+            .first()
+            .ok_or(Error::<T>::InsufficientLiquidityMinted)?
+            .saturated_into();
+        let exponent = FixedU128::checked_from_rational(1u128, amounts.len() as u128)
             .ok_or(Error::<T>::DivisionByZero)?;
 
-        // Return the calculated output amount
+        let mut mean = FixedU128::one();
+        for amount in &amounts[1..] {
+            let ratio = FixedU128::checked_from_rational(amount.saturated_into::<u128>(), first)
+                .ok_or(Error::<T>::ArithmeticOverflow)?;
+            let root = weighted_math::pow(ratio, exponent).ok_or(Error::<T>::ArithmeticOverflow)?;
+            mean = mean
+                .checked_mul(&root)
+                .ok_or(Error::<T>::ArithmeticOverflow)?;
+        }
+        LiquidityPool::<T>::mul_fixed(first, mean, Rounding::Down)
+    }
+
+    // Function to get the amounts a deposit takes and the liquidity it mints. An empty pool
+    // takes the desired amounts, otherwise the deposit mints the most liquidity all of the
+    // desired amounts cover at the ratio of the reserves.
+    pub fn deposit_amounts(
+        &self,
+        amounts_desired: &[AssetBalanceOf<T>],
+    ) -> Result<(Vec<AssetBalanceOf<T>>, AssetBalanceOf<T>), DispatchError> {
+        ensure!(
+            amounts_desired.iter().all(|amount| !amount.is_zero()),
+            Error::<T>::InsufficientLiquidityMinted
+        );
+
+        if self.total_liquidity.is_zero() {
+            let liquidity_minted = Self::invariant(amounts_desired)?;
+            ensure!(
+                liquidity_minted > T::MinimumLiquidity::get(),
+                Error::<T>::InsufficientInitialLiquidity
+            );
+            return Ok((amounts_desired.to_vec(), liquidity_minted));
+        }
+
+        let mut liquidity_minted = AssetBalanceOf::<T>::max_value();
+        for (amount, reserve) in amounts_desired.iter().zip(self.reserves.iter()) {
+            let covered = amount
+                .checked_mul(&self.total_liquidity)
+                .ok_or(Error::<T>::ArithmeticOverflow)?
+                .checked_div(reserve)
+                .ok_or(Error::<T>::DivisionByZero)?;
+            liquidity_minted = liquidity_minted.min(covered);
+        }
+        ensure!(
+            !liquidity_minted.is_zero(),
+            Error::<T>::InsufficientLiquidityMinted
+        );
+
+        // Each amount is rounded up in favour of the pool, which never exceeds the desired one
+        let amounts = self
+            .reserves
+            .iter()
+            .map(|reserve| -> Result<_, DispatchError> {
+                let numerator = liquidity_minted
+                    .checked_mul(reserve)
+                    .ok_or(Error::<T>::ArithmeticOverflow)?;
+                LiquidityPool::<T>::div_ceil(numerator, self.total_liquidity)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((amounts, liquidity_minted))
+    }
+
+    // Function to get the amount of each asset paid out for burning liquidity, proportional to
+    // the reserves and rounded down in favour of the pool
+    pub fn withdrawal_amounts(
+        &self,
+        liquidity_burned: AssetBalanceOf<T>,
+    ) -> Result<Vec<AssetBalanceOf<T>>, DispatchError> {
+        ensure!(!liquidity_burned.is_zero(), Error::<T>::ZeroLiquidityBurned);
+        ensure!(
+            !self.total_liquidity.is_zero(),
+            Error::<T>::InsufficientLiquidity
+        );
+
+        self.reserves
+            .iter()
+            .map(|reserve| -> Result<_, DispatchError> {
+                liquidity_burned
+                    .checked_mul(reserve)
+                    .ok_or(Error::<T>::ArithmeticOverflow)?
+                    .checked_div(&self.total_liquidity)
+                    .ok_or(Error::<T>::DivisionByZero.into())
+            })
+            .collect()
+    }
+
+    // Function to mint liquidity tokens and update reserves
+    pub fn mint(
+        &mut self,
+        amounts: &[AssetBalanceOf<T>],
+        liquidity_minted: AssetBalanceOf<T>,
+    ) -> DispatchResult {
+        for (index, amount) in amounts.iter().enumerate() {
+            self.reserves[index] = self.reserves[index]
+                .checked_add(amount)
+                .ok_or(Error::<T>::ReserveOverflow)?;
+        }
+        self.total_liquidity = self
+            .total_liquidity
+            .checked_add(&liquidity_minted)
+            .ok_or(Error::<T>::LiquidityOverflow)?;
+        Ok(())
+    }
+
+    // Function to burn liquidity tokens and update reserves
+    pub fn burn(
+        &mut self,
+        liquidity_burned: AssetBalanceOf<T>,
+        amounts_out: &[AssetBalanceOf<T>],
+    ) -> DispatchResult {
+        for (index, amount) in amounts_out.iter().enumerate() {
+            self.reserves[index] = self.reserves[index]
+                .checked_sub(amount)
+                .ok_or(Error::<T>::InsufficientReserves)?;
+        }
+        self.total_liquidity = self
+            .total_liquidity
+            .checked_sub(&liquidity_burned)
+            .ok_or(Error::<T>::InsufficientLiquidity)?;
+        Ok(())
+    }
+
+    // Function to swap between two assets of the pool, returns the amount out. The swap fee
+    // stays in the reserves except for the protocol's share of it, which is accrued apart.
+    pub fn swap(
+        &mut self,
+        asset_in: AssetKindOf<T>,
+        amount_in: AssetBalanceOf<T>,
+        asset_out: AssetKindOf<T>,
+        min_amount_out: AssetBalanceOf<T>,
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        let index_in = self.position(asset_in).ok_or(Error::<T>::InvalidAssetIn)?;
+        let index_out = self
+            .position(asset_out)
+            .filter(|index_out| *index_out != index_in)
+            .ok_or(Error::<T>::InvalidAssetOut)?;
+        let (reserve_in, reserve_out) = (self.reserves[index_in], self.reserves[index_out]);
+        ensure!(
+            !reserve_in.is_zero() && !reserve_out.is_zero(),
+            Error::<T>::InsufficientLiquidity
+        );
+
+        let amount_in_after_fee = amount_in
+            .checked_sub(&self.swap_fee.mul_floor(amount_in))
+            .ok_or(Error::<T>::ArithmeticOverflow)?;
+        let amount_out =
+            ConstantProduct.amount_out(amount_in_after_fee, reserve_in, reserve_out)?;
+        ensure!(
+            amount_out >= min_amount_out,
+            Error::<T>::InsufficientAmountOut
+        );
+
+        let protocol_fee = self
+            .protocol_fee
+            .mul_floor(self.swap_fee.mul_floor(amount_in));
+        self.reserves[index_in] = reserve_in
+            .checked_add(&amount_in)
+            .and_then(|reserve| reserve.checked_sub(&protocol_fee))
+            .ok_or(Error::<T>::ReserveOverflow)?;
+        self.reserves[index_out] = reserve_out
+            .checked_sub(&amount_out)
+            .ok_or(Error::<T>::InsufficientReserves)?;
+        self.protocol_fees[index_in] = self.protocol_fees[index_in]
+            .checked_add(&protocol_fee)
+            .ok_or(Error::<T>::ArithmeticOverflow)?;
         Ok(amount_out)
     }
 }
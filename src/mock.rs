@@ -1,6 +1,9 @@
 use crate as pallet_dex;
+use crate::traits::{AmmCurve, ConstantProduct, SequentialAssetIds};
 use frame_support::{
-    derive_impl, parameter_types,
+    derive_impl,
+    pallet_prelude::{Decode, DispatchError, Encode, MaxEncodedLen, RuntimeDebug, TypeInfo},
+    parameter_types,
     traits::{AsEnsureOriginWithArg, ConstU128, ConstU16, ConstU32, ConstU64},
     PalletId,
 };
@@ -8,11 +11,16 @@ use frame_system::{EnsureRoot, EnsureSigned};
 use sp_core::H256;
 use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
-    BuildStorage,
+    ArithmeticError, BuildStorage, FixedU128, Permill,
 };
 
 parameter_types! {
     pub const DexPallet: PalletId = PalletId(*b"POLKADEX");
+    pub const MinSwapFee: Permill = Permill::from_perthousand(1);
+    pub const MaxSwapFee: Permill = Permill::from_percent(10);
+    pub const MaxProtocolFee: Permill = Permill::from_percent(50);
+    pub const ProtocolFeeReceiver: u64 = TREASURY;
+    pub const PoolCreationDeposit: Balance = 100;
 }
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -64,7 +72,7 @@ impl pallet_balances::Config for Test {
     type ExistentialDeposit = ConstU128<1>;
     type AccountStore = System;
     type ReserveIdentifier = [u8; 8];
-    type RuntimeHoldReason = ();
+    type RuntimeHoldReason = RuntimeHoldReason;
     type FreezeIdentifier = ();
     type MaxLocks = ConstU32<10>;
     type MaxReserves = ();
@@ -97,15 +105,121 @@ impl pallet_assets::Config for Test {
 
 impl pallet_dex::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type RuntimeHoldReason = RuntimeHoldReason;
     type NativeBalance = Balances;
     type Fungibles = Assets;
     type PalletId = DexPallet;
+    type LiquidityTokenIdGenerator = SequentialAssetIds<ConstU32<1000>>;
+    type MinimumLiquidity = ConstU128<1000>;
+    type MinSwapFee = MinSwapFee;
+    type MaxSwapFee = MaxSwapFee;
+    type MaxProtocolFee = MaxProtocolFee;
+    type PoolCreateOrigin = EnsureSigned<Self::AccountId>;
+    type PauseOrigin = EnsureRoot<Self::AccountId>;
+    type AdminOrigin = EnsureRoot<Self::AccountId>;
+    type ProtocolFeeReceiver = ProtocolFeeReceiver;
+    type MaxSwapPathLength = ConstU32<4>;
+    type PoolCreationDeposit = PoolCreationDeposit;
+    type MaxPriceObservations = ConstU32<8>;
+    type MaxAmplification = ConstU32<1_000_000>;
+    type MinAmplificationRampDuration = ConstU64<10>;
+    type MaxAssetsPerPool = ConstU32<4>;
+    type MaxInitializedTicks = ConstU32<64>;
+    type Curve = TestCurve;
+    type WeightInfo = ();
+}
+
+// The curves pools of the mock runtime can be created with
+#[derive(
+    Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub enum TestCurve {
+    #[default]
+    ConstantProduct,
+    // The sum of the reserves is kept constant, swapping one to one
+    ConstantSum,
+}
+
+impl AmmCurve<Balance> for TestCurve {
+    fn amount_out(
+        &self,
+        amount_in: Balance,
+        reserve_in: Balance,
+        reserve_out: Balance,
+    ) -> Result<Balance, DispatchError> {
+        match self {
+            TestCurve::ConstantProduct => {
+                ConstantProduct.amount_out(amount_in, reserve_in, reserve_out)
+            }
+            TestCurve::ConstantSum => {
+                frame_support::ensure!(amount_in < reserve_out, ArithmeticError::Underflow);
+                Ok(amount_in)
+            }
+        }
+    }
+
+    fn amount_in(
+        &self,
+        amount_out: Balance,
+        reserve_in: Balance,
+        reserve_out: Balance,
+    ) -> Result<Balance, DispatchError> {
+        match self {
+            TestCurve::ConstantProduct => {
+                ConstantProduct.amount_in(amount_out, reserve_in, reserve_out)
+            }
+            TestCurve::ConstantSum => Ok(amount_out),
+        }
+    }
+
+    fn mint_shares(
+        &self,
+        amounts: (Balance, Balance),
+        reserves: (Balance, Balance),
+        total_shares: Balance,
+    ) -> Result<Balance, DispatchError> {
+        match self {
+            TestCurve::ConstantSum if total_shares == 0 => {
+                let shares = amounts.0.checked_add(amounts.1);
+                Ok(shares.ok_or(ArithmeticError::Overflow)?)
+            }
+            _ => ConstantProduct.mint_shares(amounts, reserves, total_shares),
+        }
+    }
+
+    fn burn_amounts(
+        &self,
+        shares: Balance,
+        reserves: (Balance, Balance),
+        total_shares: Balance,
+    ) -> Result<(Balance, Balance), DispatchError> {
+        ConstantProduct.burn_amounts(shares, reserves, total_shares)
+    }
+
+    fn spot_price(&self, reserve_in: Balance, reserve_out: Balance) -> FixedU128 {
+        match self {
+            TestCurve::ConstantProduct => {
+                AmmCurve::<Balance>::spot_price(&ConstantProduct, reserve_in, reserve_out)
+            }
+            TestCurve::ConstantSum => FixedU128::from_u32(1),
+        }
+    }
 }
 
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+pub const TREASURY: u64 = 99;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    frame_system::GenesisConfig::<Test>::default()
+    let mut t = frame_system::GenesisConfig::<Test>::default()
         .build_storage()
-        .unwrap()
-        .into()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(ALICE, 1_000_000), (BOB, 1_000_000), (CHARLIE, 1_000_000)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    t.into()
 }
@@ -0,0 +1,111 @@
+use super::*;
+use frame_support::traits::fungible::Mutate as NativeMutate;
+use frame_support::traits::fungibles::Mutate as AssetsMutate;
+use frame_support::traits::tokens::Preservation;
+use sp_std::marker::PhantomData;
+
+/// An asset that can be traded in a pool, either the chain's native currency or an asset of
+/// `Config::Fungibles`. The native currency sorts before every other asset.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    RuntimeDebug,
+    MaxEncodedLen,
+    TypeInfo,
+)]
+pub enum NativeOrAssetId<AssetId> {
+    Native,
+    Asset(AssetId),
+}
+
+impl<AssetId> From<AssetId> for NativeOrAssetId<AssetId> {
+    fn from(asset_id: AssetId) -> Self {
+        NativeOrAssetId::Asset(asset_id)
+    }
+}
+
+/// Union of `Config::NativeBalance` and `Config::Fungibles`, routing every operation on a
+/// `NativeOrAssetId` to the right one. Native amounts are expressed in `AssetBalanceOf<T>`
+/// like any other pool amount and converted on the way in and out.
+pub struct NativeOrAssets<T>(PhantomData<T>);
+
+impl<T: Config> NativeOrAssets<T> {
+    /// Returns the balance of `who` in the given asset.
+    pub fn balance(
+        asset: AssetKindOf<T>,
+        who: &AccountIdOf<T>,
+    ) -> Result<AssetBalanceOf<T>, DispatchError> {
+        match asset {
+            NativeOrAssetId::Native => {
+                Self::to_asset_balance(<T::NativeBalance as fungible::Inspect<_>>::balance(who))
+            }
+            NativeOrAssetId::Asset(asset_id) => Ok(
+                <T::Fungibles as fungibles::Inspect<_>>::balance(asset_id, who),
+            ),
+        }
+    }
+
+    /// Returns whether the given asset exists, the native currency always does.
+    pub fn asset_exists(asset: AssetKindOf<T>) -> bool {
+        match asset {
+            NativeOrAssetId::Native => true,
+            NativeOrAssetId::Asset(asset_id) => {
+                <T::Fungibles as fungibles::Inspect<_>>::asset_exists(asset_id)
+            }
+        }
+    }
+
+    /// Transfers `amount` of the given asset from `source` to `dest`.
+    pub fn transfer(
+        asset: AssetKindOf<T>,
+        source: &AccountIdOf<T>,
+        dest: &AccountIdOf<T>,
+        amount: AssetBalanceOf<T>,
+        preservation: Preservation,
+    ) -> DispatchResult {
+        match asset {
+            NativeOrAssetId::Native => {
+                let amount = Self::to_native_balance(amount)?;
+                <T::NativeBalance as NativeMutate<_>>::transfer(
+                    source,
+                    dest,
+                    amount,
+                    preservation,
+                )?;
+            }
+            NativeOrAssetId::Asset(asset_id) => {
+                <T::Fungibles as AssetsMutate<_>>::transfer(
+                    asset_id,
+                    source,
+                    dest,
+                    amount,
+                    preservation,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Converts a native balance into the balance type used by pools.
+    pub fn to_asset_balance(amount: BalanceOf<T>) -> Result<AssetBalanceOf<T>, DispatchError> {
+        let amount: u128 = amount
+            .try_into()
+            .map_err(|_| Error::<T>::BalanceConversionFailed)?;
+        AssetBalanceOf::<T>::try_from(amount)
+            .map_err(|_| Error::<T>::BalanceConversionFailed.into())
+    }
+
+    /// Converts a pool balance into the native balance type.
+    pub fn to_native_balance(amount: AssetBalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+        let amount: u128 = amount
+            .try_into()
+            .map_err(|_| Error::<T>::BalanceConversionFailed)?;
+        BalanceOf::<T>::try_from(amount).map_err(|_| Error::<T>::BalanceConversionFailed.into())
+    }
+}
@@ -0,0 +1,35 @@
+//! Runtime API for querying the DEX pallet.
+
+use codec::Codec;
+use sp_runtime::FixedU128;
+
+sp_api::decl_runtime_apis! {
+    /// Quotes and pool state, computed by the same code that executes swaps.
+    pub trait DexApi<AssetKind, Balance, AssetId, Pool, BlockNumber>
+    where
+        AssetKind: Codec,
+        Balance: Codec,
+        AssetId: Codec,
+        Pool: Codec,
+        BlockNumber: Codec,
+    {
+        /// Returns the amount of `asset_out` received for swapping exactly `amount_in`.
+        fn quote_exact_in(asset_in: AssetKind, asset_out: AssetKind, amount_in: Balance) -> Option<Balance>;
+
+        /// Returns the amount of `asset_in` required to receive exactly `amount_out`.
+        fn quote_exact_out(asset_in: AssetKind, asset_out: AssetKind, amount_out: Balance) -> Option<Balance>;
+
+        /// Returns the reserves of the pool for the pair, ordered like the pair's assets.
+        fn get_reserves(asset_a: AssetKind, asset_b: AssetKind) -> Option<(Balance, Balance)>;
+
+        /// Returns the pool for the pair.
+        fn get_pool(asset_a: AssetKind, asset_b: AssetKind) -> Option<Pool>;
+
+        /// Returns the liquidity token issued by the pool for the pair.
+        fn lp_token_for_pair(asset_a: AssetKind, asset_b: AssetKind) -> Option<AssetId>;
+
+        /// Returns the time-weighted average price of `base` in `quote` over the last `window`
+        /// blocks.
+        fn twap(base: AssetKind, quote: AssetKind, window: BlockNumber) -> Option<FixedU128>;
+    }
+}
@@ -0,0 +1,133 @@
+// Math of the StableSwap invariant for pools of two assets with reserves `x` and `y`:
+//
+//     A·n^n·(x + y) + D = A·D·n^n + D^(n+1) / (n^n·x·y),  with n = 2
+//
+// The invariant behaves like a constant sum close to balanced reserves and like a constant
+// product far from it, the amplification coefficient `A` controls where the transition happens.
+// `D` and the reserve after a swap are solved with Newton's method, giving up after a bounded
+// number of iterations. Intermediate values are computed in 256 bits.
+
+use sp_core::U256;
+use sp_runtime::{FixedPointNumber, FixedU128};
+
+/// The number of Newton iterations after which a solver gives up
+pub const MAX_ITERATIONS: u32 = 255;
+
+// The number of assets in a pool
+const N_COINS: u64 = 2;
+
+// Helper function to get `A·n^n`
+fn amplification_factor(amplification: u32) -> Option<U256> {
+    if amplification == 0 {
+        return None;
+    }
+    U256::from(amplification).checked_mul(U256::from(N_COINS * N_COINS))
+}
+
+// Helper function to check whether two successive approximations are within one unit
+fn converged(current: U256, previous: U256) -> bool {
+    if current > previous {
+        current - previous <= U256::one()
+    } else {
+        previous - current <= U256::one()
+    }
+}
+
+// Function to compute the invariant `D` of the reserves, `None` if a reserve is empty or the
+// iteration does not converge
+pub fn compute_d(reserves: (u128, u128), amplification: u32) -> Option<u128> {
+    let (x, y) = (U256::from(reserves.0), U256::from(reserves.1));
+    if x.is_zero() || y.is_zero() {
+        return None;
+    }
+
+    let n = U256::from(N_COINS);
+    let ann = amplification_factor(amplification)?;
+    let sum = x.checked_add(y)?;
+
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        // D^(n+1) / (n^n·x·y), dividing by one reserve at a time to stay in range
+        let d_product = d
+            .checked_mul(d)?
+            .checked_div(x.checked_mul(n)?)?
+            .checked_mul(d)?
+            .checked_div(y.checked_mul(n)?)?;
+
+        let previous = d;
+        let numerator = ann
+            .checked_mul(sum)?
+            .checked_add(d_product.checked_mul(n)?)?
+            .checked_mul(d)?;
+        let denominator = ann
+            .checked_sub(U256::one())?
+            .checked_mul(d)?
+            .checked_add(n.checked_add(U256::one())?.checked_mul(d_product)?)?;
+        d = numerator.checked_div(denominator)?;
+
+        if converged(d, previous) {
+            return d.try_into().ok();
+        }
+    }
+
+    None
+}
+
+// Function to compute the reserve of one asset that keeps the invariant `d` given the reserve
+// `x` of the other one, `None` if the iteration does not converge
+pub fn compute_y(x: u128, d: u128, amplification: u32) -> Option<u128> {
+    let (x, d) = (U256::from(x), U256::from(d));
+    if x.is_zero() {
+        return None;
+    }
+
+    let n = U256::from(N_COINS);
+    let ann = amplification_factor(amplification)?;
+
+    // y² + (b - D)·y = c, solved as y = (y² + c) / (2y + b - D)
+    let c = d
+        .checked_mul(d)?
+        .checked_div(x.checked_mul(n)?)?
+        .checked_mul(d)?
+        .checked_div(ann.checked_mul(n)?)?;
+    let b = x.checked_add(d.checked_div(ann)?)?;
+
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let previous = y;
+        let numerator = y.checked_mul(y)?.checked_add(c)?;
+        let denominator = y.checked_mul(n)?.checked_add(b)?.checked_sub(d)?;
+        y = numerator.checked_div(denominator)?;
+
+        if converged(y, previous) {
+            return y.try_into().ok();
+        }
+    }
+
+    None
+}
+
+// Function to get the marginal price of the first asset in the second as the inner value of
+// `FixedU128`, `None` if a reserve is empty
+pub fn spot_price(reserves: (u128, u128), amplification: u32) -> Option<u128> {
+    let d = U256::from(compute_d(reserves, amplification)?);
+    let (x, y) = (U256::from(reserves.0), U256::from(reserves.1));
+    let ann = amplification_factor(amplification)?;
+
+    // The ratio of the partial derivatives of the invariant by x and by y, both multiplied by
+    // n^n·x·y to keep them integral
+    let scaled_product = ann
+        .checked_mul(U256::from(N_COINS * N_COINS))?
+        .checked_mul(x)?
+        .checked_mul(y)?;
+    let numerator =
+        scaled_product.checked_add(d.checked_mul(d)?.checked_div(x)?.checked_mul(d)?)?;
+    let denominator =
+        scaled_product.checked_add(d.checked_mul(d)?.checked_div(y)?.checked_mul(d)?)?;
+
+    numerator
+        .checked_mul(U256::from(FixedU128::DIV))?
+        .checked_div(denominator)?
+        .try_into()
+        .ok()
+}
@@ -1,5 +1,86 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::asset_listing::AssetListing;
+use crate::concentrated_liquidity::MIN_TICK;
+use crate::liquidity_pool::{AssetPair, ConcentratedLiquidity, PoolKind, PoolStatus, ReserveDrift};
+use crate::native_or_asset::{NativeOrAssetId, NativeOrAssets};
+use crate::traits::{AmmCurve, ConstantProduct};
+use crate::{
+    mock::*, AllowlistEnforced, AssetListings, Error, Event, GlobalStatus, HoldReason,
+    LiquidityPools, LiquidityTokens, MultiAssetLiquidityTokens, MultiAssetPools,
+    NextLiquidityTokenId, Positions, PriceObservations, Ticks, WeightInfo,
+    MAX_LIQUIDITY_TOKEN_ID_ATTEMPTS,
+};
+use frame_support::dispatch::DispatchResultWithPostInfo;
+use frame_support::traits::fungible::InspectHold;
+use frame_support::traits::fungibles::roles::Inspect as RolesInspect;
+use frame_support::traits::fungibles::Inspect;
+use frame_support::traits::fungibles::Mutate;
+use frame_support::traits::tokens::Preservation;
+use frame_support::traits::ConstU32;
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_runtime::traits::AccountIdConversion;
+use sp_runtime::{DispatchError, DispatchResult, FixedPointNumber, FixedU128, Permill, TokenError};
+
+type AssetKind = NativeOrAssetId<u32>;
+
+const NATIVE: AssetKind = NativeOrAssetId::Native;
+const ASSET_A: AssetKind = NativeOrAssetId::Asset(1);
+const ASSET_B: AssetKind = NativeOrAssetId::Asset(2);
+const ASSET_C: AssetKind = NativeOrAssetId::Asset(3);
+const LP_TOKEN: u32 = 1000;
+const SWAP_FEE: Permill = Permill::from_perthousand(3);
+
+fn asset_id(asset: AssetKind) -> u32 {
+    match asset {
+        NativeOrAssetId::Asset(id) => id,
+        NativeOrAssetId::Native => panic!("the native currency has no asset id"),
+    }
+}
+
+fn create_asset(asset: AssetKind) {
+    assert_ok!(Assets::force_create(
+        RuntimeOrigin::root(),
+        asset_id(asset).into(),
+        ALICE,
+        true,
+        1
+    ));
+}
+
+fn mint_asset(asset: AssetKind, who: u64, amount: Balance) {
+    assert_ok!(<Assets as Mutate<u64>>::mint_into(
+        asset_id(asset),
+        &who,
+        amount
+    ));
+}
+
+fn balance(asset: AssetKind, who: u64) -> Balance {
+    NativeOrAssets::<Test>::balance(asset, &who).unwrap()
+}
+
+fn pallet_account() -> u64 {
+    DexPallet::get().into_account_truncating()
+}
+
+fn pool_account() -> u64 {
+    Dex::pool_account_id(&AssetPair::new(ASSET_A, ASSET_B))
+}
+
+// Creates the A/B pool and funds Alice and Bob with both assets
+fn setup_pool() {
+    create_asset(ASSET_A);
+    create_asset(ASSET_B);
+    for who in [ALICE, BOB] {
+        mint_asset(ASSET_A, who, 1_000_000);
+        mint_asset(ASSET_B, who, 1_000_000);
+    }
+    assert_ok!(Dex::create_liquidity_pool(
+        RuntimeOrigin::signed(ALICE),
+        ASSET_A,
+        ASSET_B,
+        SWAP_FEE
+    ));
+}
 
 #[test]
 fn basic_test() {
@@ -9,3 +90,3866 @@ fn basic_test() {
         // Future test conditions would go here.
     });
 }
+
+#[test]
+fn create_liquidity_pool_creates_liquidity_token() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+
+        assert!(<Assets as Inspect<u64>>::asset_exists(LP_TOKEN));
+        assert_eq!(
+            <Assets as RolesInspect<u64>>::owner(LP_TOKEN),
+            Some(pallet_account())
+        );
+        assert_eq!(
+            LiquidityTokens::<Test>::get(LP_TOKEN),
+            Some(AssetPair::new(ASSET_A, ASSET_B))
+        );
+        assert_eq!(
+            Dex::pool_by_liquidity_token(LP_TOKEN).map(|pool| pool.liquidity_token),
+            Some(LP_TOKEN)
+        );
+        assert_eq!(NextLiquidityTokenId::<Test>::get(), Some(LP_TOKEN + 1));
+        System::assert_last_event(
+            Event::LiquidityPoolCreated(ALICE, ASSET_A, ASSET_B, LP_TOKEN).into(),
+        );
+
+        // The next pool receives the next liquidity token id
+        create_asset(ASSET_C);
+        assert_ok!(Dex::create_liquidity_pool(
+            RuntimeOrigin::signed(BOB),
+            ASSET_C,
+            ASSET_A,
+            SWAP_FEE
+        ));
+        assert_eq!(
+            LiquidityTokens::<Test>::get(LP_TOKEN + 1),
+            Some(AssetPair::new(ASSET_A, ASSET_C))
+        );
+        assert_eq!(Dex::pool_by_liquidity_token(LP_TOKEN + 2), None);
+    });
+}
+
+#[test]
+fn create_liquidity_pool_rejects_existing_pool() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_noop!(
+            Dex::create_liquidity_pool(RuntimeOrigin::signed(BOB), ASSET_B, ASSET_A, SWAP_FEE),
+            Error::<Test>::LiquidityPoolAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn create_liquidity_pool_rejects_identical_assets() {
+    new_test_ext().execute_with(|| {
+        create_asset(ASSET_A);
+        assert_noop!(
+            Dex::create_liquidity_pool(RuntimeOrigin::signed(ALICE), ASSET_A, ASSET_A, SWAP_FEE),
+            Error::<Test>::IdenticalAssets
+        );
+        assert_noop!(
+            Dex::create_liquidity_pool(RuntimeOrigin::signed(ALICE), NATIVE, NATIVE, SWAP_FEE),
+            Error::<Test>::IdenticalAssets
+        );
+    });
+}
+
+#[test]
+fn create_liquidity_pool_rejects_missing_assets() {
+    new_test_ext().execute_with(|| {
+        create_asset(ASSET_A);
+        assert_noop!(
+            Dex::create_liquidity_pool(RuntimeOrigin::signed(ALICE), ASSET_A, ASSET_B, SWAP_FEE),
+            Error::<Test>::AssetNotFound
+        );
+        assert_noop!(
+            Dex::create_multi_asset_pool(
+                RuntimeOrigin::signed(ALICE),
+                BoundedVec::try_from(vec![NATIVE, ASSET_A, ASSET_B]).unwrap(),
+                SWAP_FEE
+            ),
+            Error::<Test>::AssetNotFound
+        );
+
+        // The native currency always exists
+        assert_ok!(Dex::create_liquidity_pool(
+            RuntimeOrigin::signed(ALICE),
+            NATIVE,
+            ASSET_A,
+            SWAP_FEE
+        ));
+    });
+}
+
+#[test]
+fn create_liquidity_pool_skips_taken_liquidity_tokens() {
+    new_test_ext().execute_with(|| {
+        create_asset(ASSET_A);
+        create_asset(ASSET_B);
+        create_asset(LP_TOKEN.into());
+        create_asset((LP_TOKEN + 1).into());
+        assert_ok!(Dex::create_liquidity_pool(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            SWAP_FEE
+        ));
+        assert_eq!(Dex::lp_token_for_pair(ASSET_A, ASSET_B), Some(LP_TOKEN + 2));
+        assert_eq!(NextLiquidityTokenId::<Test>::get(), Some(LP_TOKEN + 3));
+
+        // Only a bounded number of taken ids are skipped per call, but they stay skipped
+        System::set_block_number(1);
+        create_asset(ASSET_C);
+        let squatted = LP_TOKEN + 3 + MAX_LIQUIDITY_TOKEN_ID_ATTEMPTS;
+        for id in LP_TOKEN + 3..squatted {
+            create_asset(id.into());
+        }
+        assert_ok!(Dex::create_liquidity_pool(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_C,
+            SWAP_FEE
+        ));
+        assert_eq!(Dex::lp_token_for_pair(ASSET_A, ASSET_C), None);
+        assert_eq!(NextLiquidityTokenId::<Test>::get(), Some(squatted));
+        System::assert_last_event(Event::LiquidityTokenIdsSkipped(squatted).into());
+
+        assert_ok!(Dex::create_liquidity_pool(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_C,
+            SWAP_FEE
+        ));
+        assert_eq!(Dex::lp_token_for_pair(ASSET_A, ASSET_C), Some(squatted));
+    });
+}
+
+#[test]
+fn burn_liquidity_pays_out_assets() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            40_000,
+            0,
+            0,
+            None
+        ));
+        assert_eq!(Assets::balance(LP_TOKEN, ALICE), 19_000);
+
+        let pool_a = balance(ASSET_A, pool_account());
+        let pool_b = balance(ASSET_B, pool_account());
+        let alice_a = balance(ASSET_A, ALICE);
+        let alice_b = balance(ASSET_B, ALICE);
+
+        assert_ok!(Dex::burn_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            5_000,
+            2_500,
+            10_000,
+            None,
+            None
+        ));
+
+        assert_eq!(Assets::balance(LP_TOKEN, ALICE), 14_000);
+        assert_eq!(balance(ASSET_A, ALICE), alice_a + 2_500);
+        assert_eq!(balance(ASSET_B, ALICE), alice_b + 10_000);
+        assert_eq!(balance(ASSET_A, pool_account()), pool_a - 2_500);
+        assert_eq!(balance(ASSET_B, pool_account()), pool_b - 10_000);
+
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.reserves, (7_500, 30_000));
+        assert_eq!(pool.total_liquidity, 15_000);
+
+        System::assert_last_event(
+            Event::LiquidityBurned(ALICE, ALICE, ASSET_A, ASSET_B, 5_000, 2_500, 10_000).into(),
+        );
+    });
+}
+
+#[test]
+fn burn_liquidity_pays_out_to_beneficiary() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+
+        assert_ok!(Dex::burn_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            9_000,
+            0,
+            0,
+            Some(CHARLIE),
+            None
+        ));
+
+        assert_eq!(balance(ASSET_A, CHARLIE), 9_000);
+        assert_eq!(balance(ASSET_B, CHARLIE), 9_000);
+        assert_eq!(balance(ASSET_A, pool_account()), 1_000);
+        assert_eq!(balance(ASSET_B, pool_account()), 1_000);
+        System::assert_last_event(
+            Event::LiquidityBurned(ALICE, CHARLIE, ASSET_A, ASSET_B, 9_000, 9_000, 9_000).into(),
+        );
+    });
+}
+
+#[test]
+fn burn_liquidity_respects_minimum_amounts() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+
+        assert_noop!(
+            Dex::burn_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                ASSET_A,
+                ASSET_B,
+                5_000,
+                5_001,
+                0,
+                None,
+                None
+            ),
+            Error::<Test>::InsufficientAmountsOut
+        );
+    });
+}
+
+#[test]
+fn each_pool_has_its_own_account() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        create_asset(ASSET_C);
+        mint_asset(ASSET_C, ALICE, 1_000_000);
+        assert_ok!(Dex::create_liquidity_pool(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_C,
+            SWAP_FEE
+        ));
+        let other_pool_account = Dex::pool_account_id(&AssetPair::new(ASSET_A, ASSET_C));
+        assert_ne!(pool_account(), other_pool_account);
+        assert_ne!(pool_account(), pallet_account());
+        assert_eq!(
+            Dex::pool_account_id(&AssetPair::new(ASSET_B, ASSET_A)),
+            pool_account()
+        );
+
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_C,
+            20_000,
+            20_000,
+            0,
+            0,
+            None
+        ));
+        assert_eq!(balance(ASSET_A, pool_account()), 10_000);
+        assert_eq!(balance(ASSET_A, other_pool_account), 20_000);
+        assert_eq!(balance(ASSET_A, pallet_account()), 0);
+
+        // Swaps only move funds of the pool they trade against
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            1_000,
+            0,
+            None
+        ));
+        assert_eq!(balance(ASSET_A, pool_account()), 11_000);
+        assert_eq!(balance(ASSET_A, other_pool_account), 20_000);
+        assert_eq!(balance(ASSET_C, other_pool_account), 20_000);
+    });
+}
+
+#[test]
+fn first_mint_locks_minimum_liquidity() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.total_liquidity, 10_000);
+        assert_eq!(Assets::balance(LP_TOKEN, ALICE), 9_000);
+        assert_eq!(Assets::balance(LP_TOKEN, pool_account()), 1_000);
+        assert_eq!(Assets::total_supply(LP_TOKEN), pool.total_liquidity);
+        System::assert_last_event(
+            Event::LiquidityMinted(ALICE, ASSET_A, ASSET_B, 9_000, 10_000, 10_000).into(),
+        );
+
+        // Later mints are not charged the minimum liquidity again
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+        assert_eq!(Assets::balance(LP_TOKEN, BOB), 10_000);
+        assert_eq!(Assets::balance(LP_TOKEN, pool_account()), 1_000);
+    });
+}
+
+#[test]
+fn first_mint_below_minimum_liquidity_fails() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_noop!(
+            Dex::mint_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                ASSET_A,
+                ASSET_B,
+                1_000,
+                1_000,
+                0,
+                None
+            ),
+            Error::<Test>::InsufficientInitialLiquidity
+        );
+        assert_noop!(
+            Dex::mint_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                ASSET_A,
+                ASSET_B,
+                100,
+                9_000,
+                0,
+                None
+            ),
+            Error::<Test>::InsufficientInitialLiquidity
+        );
+    });
+}
+
+#[test]
+fn first_depositor_share_inflation_is_neutralised() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        mint_asset(ASSET_A, ALICE, 1_000_000_000);
+        mint_asset(ASSET_B, ALICE, 1_000_000_000);
+        mint_asset(ASSET_A, BOB, 1_000_000_000);
+        mint_asset(ASSET_B, BOB, 1_000_000_000);
+        let bob_a = balance(ASSET_A, BOB);
+        let bob_b = balance(ASSET_B, BOB);
+
+        // The attacker mints the smallest possible share...
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            1_001,
+            1_001,
+            0,
+            0,
+            None
+        ));
+        assert_eq!(Assets::balance(LP_TOKEN, BOB), 1);
+
+        // ...and donates to the pool to inflate the value of a single share
+        donate(ASSET_A, 1_000_000_000);
+        donate(ASSET_B, 1_000_000_000);
+        assert_ok!(Dex::sync(RuntimeOrigin::signed(BOB), ASSET_A, ASSET_B));
+
+        // The victim deposits and is still issued a meaningful amount of shares
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            700_000_000,
+            700_000_000,
+            0,
+            0,
+            None
+        ));
+        let victim_liquidity = Assets::balance(LP_TOKEN, ALICE);
+        assert_eq!(victim_liquidity, 700);
+
+        // The donation mostly accrues to the locked liquidity, so the attack is a loss
+        let attacker_liquidity = Assets::balance(LP_TOKEN, BOB);
+        assert_ok!(Dex::burn_liquidity(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            attacker_liquidity,
+            0,
+            0,
+            None,
+            None
+        ));
+        assert!(balance(ASSET_A, BOB) < bob_a - 990_000_000);
+        assert!(balance(ASSET_B, BOB) < bob_b - 990_000_000);
+
+        // And the victim withdraws almost everything they deposited
+        let alice_a = balance(ASSET_A, ALICE);
+        assert_ok!(Dex::burn_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            victim_liquidity,
+            0,
+            0,
+            None,
+            None
+        ));
+        assert!(balance(ASSET_A, ALICE) - alice_a >= 699_300_000);
+    });
+}
+
+#[test]
+fn mint_liquidity_only_takes_the_optimal_amounts() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            40_000,
+            0,
+            0,
+            None
+        ));
+
+        let bob_a = balance(ASSET_A, BOB);
+        let bob_b = balance(ASSET_B, BOB);
+        // Too much of asset B is offered, only the amount matching asset A is taken
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            1_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+        assert_eq!(balance(ASSET_A, BOB), bob_a - 1_000);
+        assert_eq!(balance(ASSET_B, BOB), bob_b - 4_000);
+        assert_eq!(Assets::balance(LP_TOKEN, BOB), 2_000);
+        System::assert_last_event(
+            Event::LiquidityMinted(BOB, ASSET_A, ASSET_B, 2_000, 1_000, 4_000).into(),
+        );
+
+        // Too much of asset A is offered, only the amount matching asset B is taken
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            5_000,
+            4_000,
+            0,
+            0,
+            None
+        ));
+        assert_eq!(balance(ASSET_A, BOB), bob_a - 2_000);
+        assert_eq!(balance(ASSET_B, BOB), bob_b - 8_000);
+        assert_eq!(Assets::balance(LP_TOKEN, BOB), 4_000);
+
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.reserves, (12_000, 48_000));
+        assert_eq!(balance(ASSET_A, pool_account()), 12_000);
+        assert_eq!(balance(ASSET_B, pool_account()), 48_000);
+    });
+}
+
+#[test]
+fn mint_liquidity_respects_minimum_amounts() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            40_000,
+            0,
+            0,
+            None
+        ));
+
+        assert_noop!(
+            Dex::mint_liquidity(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                1_000,
+                10_000,
+                0,
+                5_000,
+                None
+            ),
+            Error::<Test>::InsufficientAmountB
+        );
+        assert_noop!(
+            Dex::mint_liquidity(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                5_000,
+                4_000,
+                2_000,
+                0,
+                None
+            ),
+            Error::<Test>::InsufficientAmountA
+        );
+    });
+}
+
+#[test]
+fn create_liquidity_pool_rejects_swap_fee_out_of_bounds() {
+    new_test_ext().execute_with(|| {
+        create_asset(ASSET_A);
+        create_asset(ASSET_B);
+        assert_noop!(
+            Dex::create_liquidity_pool(
+                RuntimeOrigin::signed(ALICE),
+                ASSET_A,
+                ASSET_B,
+                Permill::zero()
+            ),
+            Error::<Test>::InvalidSwapFee
+        );
+        assert_noop!(
+            Dex::create_liquidity_pool(
+                RuntimeOrigin::signed(ALICE),
+                ASSET_A,
+                ASSET_B,
+                Permill::from_percent(11)
+            ),
+            Error::<Test>::InvalidSwapFee
+        );
+    });
+}
+
+#[test]
+fn swap_uses_the_pool_swap_fee() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+
+        // 0.3% fee: 997 * 10_000 / 10_997
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            1_000,
+            0,
+            None
+        ));
+        System::assert_last_event(Event::Swapped(BOB, ASSET_A, 1_000, ASSET_B, 906).into());
+
+        assert_ok!(Dex::set_swap_fee(
+            RuntimeOrigin::root(),
+            ASSET_B,
+            ASSET_A,
+            Permill::from_percent(10)
+        ));
+        System::assert_last_event(
+            Event::SwapFeeUpdated(ASSET_A, ASSET_B, Permill::from_percent(10)).into(),
+        );
+
+        // 10% fee: 900 * 9_094 / 11_900
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            1_000,
+            0,
+            None
+        ));
+        System::assert_last_event(Event::Swapped(BOB, ASSET_A, 1_000, ASSET_B, 687).into());
+    });
+}
+
+#[test]
+fn set_swap_fee_is_restricted() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_noop!(
+            Dex::set_swap_fee(
+                RuntimeOrigin::signed(ALICE),
+                ASSET_A,
+                ASSET_B,
+                Permill::from_percent(1)
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Dex::set_swap_fee(
+                RuntimeOrigin::root(),
+                ASSET_A,
+                ASSET_B,
+                Permill::from_percent(20)
+            ),
+            Error::<Test>::InvalidSwapFee
+        );
+        assert_noop!(
+            Dex::set_swap_fee(
+                RuntimeOrigin::root(),
+                ASSET_A,
+                ASSET_C,
+                Permill::from_percent(1)
+            ),
+            Error::<Test>::LiquidityPoolNotFound
+        );
+    });
+}
+
+#[test]
+fn protocol_fee_is_off_by_default() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            100_000,
+            100_000,
+            0,
+            0,
+            None
+        ));
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            0,
+            None
+        ));
+
+        assert_eq!(balance(ASSET_A, TREASURY), 0);
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.reserves.0, 110_000);
+    });
+}
+
+#[test]
+fn protocol_fee_is_diverted_to_the_receiver() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            100_000,
+            100_000,
+            0,
+            0,
+            None
+        ));
+        assert_ok!(Dex::set_protocol_fee(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            ASSET_B,
+            Permill::from_percent(50)
+        ));
+        System::assert_last_event(
+            Event::ProtocolFeeUpdated(ASSET_A, ASSET_B, Permill::from_percent(50)).into(),
+        );
+
+        // The swap fee is 30, half of which accrues to the protocol in the pool account
+        let bob_a = balance(ASSET_A, BOB);
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            0,
+            None
+        ));
+        System::assert_last_event(Event::Swapped(BOB, ASSET_A, 10_000, ASSET_B, 9_066).into());
+        System::assert_has_event(Event::ProtocolFeeAccrued(ASSET_A, ASSET_B, ASSET_A, 15).into());
+
+        assert_eq!(balance(ASSET_A, BOB), bob_a - 10_000);
+        assert_eq!(balance(ASSET_A, TREASURY), 0);
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.reserves, (109_985, 90_934));
+        assert_eq!(pool.protocol_fees, (15, 0));
+        assert_eq!(balance(ASSET_A, pool_account()), 110_000);
+        assert_eq!(
+            Dex::reserve_drift(ASSET_A, ASSET_B).unwrap(),
+            ReserveDrift::new((109_985, 90_934), (109_985, 90_934))
+        );
+
+        // Anyone can pay the accrued fees out to the receiver
+        assert_ok!(Dex::claim_protocol_fees(
+            RuntimeOrigin::signed(CHARLIE),
+            ASSET_A,
+            ASSET_B
+        ));
+        System::assert_last_event(Event::ProtocolFeeCollected(TREASURY, ASSET_A, 15).into());
+        assert_eq!(balance(ASSET_A, TREASURY), 15);
+        assert_eq!(balance(ASSET_A, pool_account()), 109_985);
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.protocol_fees, (0, 0));
+
+        // Switching the protocol fee off again leaves the whole fee to the LPs
+        assert_ok!(Dex::set_protocol_fee(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            ASSET_B,
+            Permill::zero()
+        ));
+        System::reset_events();
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            0,
+            None
+        ));
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.protocol_fees, (0, 0));
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::Dex(Event::ProtocolFeeAccrued(..))
+        )));
+    });
+}
+
+#[test]
+fn protocol_fee_below_the_minimum_balance_does_not_block_swaps() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        // The receiver holds none of asset A, which cannot be held below 100
+        assert_ok!(Assets::force_create(
+            RuntimeOrigin::root(),
+            asset_id(ASSET_A).into(),
+            ALICE,
+            true,
+            100
+        ));
+        create_asset(ASSET_B);
+        for who in [ALICE, BOB] {
+            mint_asset(ASSET_A, who, 1_000_000);
+            mint_asset(ASSET_B, who, 1_000_000);
+        }
+        assert_ok!(Dex::create_liquidity_pool(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            SWAP_FEE
+        ));
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            100_000,
+            100_000,
+            0,
+            0,
+            None
+        ));
+        assert_ok!(Dex::set_protocol_fee(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            ASSET_B,
+            Permill::from_percent(50)
+        ));
+
+        // Each swap accrues a fee of 15, too little to be paid out on its own
+        assert_ok!(swap(BOB, 10_000));
+        assert_noop!(
+            Dex::claim_protocol_fees(RuntimeOrigin::signed(BOB), ASSET_A, ASSET_B),
+            TokenError::BelowMinimum
+        );
+        for _ in 0..6 {
+            assert_ok!(swap(BOB, 10_000));
+        }
+        assert_ok!(Dex::claim_protocol_fees(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B
+        ));
+        assert_eq!(balance(ASSET_A, TREASURY), 105);
+    });
+}
+
+#[test]
+fn set_protocol_fee_is_bounded() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_noop!(
+            Dex::set_protocol_fee(
+                RuntimeOrigin::root(),
+                ASSET_A,
+                ASSET_B,
+                Permill::from_percent(51)
+            ),
+            Error::<Test>::InvalidProtocolFee
+        );
+    });
+}
+
+#[test]
+fn set_protocol_fee_is_restricted() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_noop!(
+            Dex::set_protocol_fee(
+                RuntimeOrigin::signed(ALICE),
+                ASSET_A,
+                ASSET_B,
+                Permill::from_percent(50)
+            ),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn swap_exact_out_buys_the_exact_amount() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            100_000,
+            100_000,
+            0,
+            0,
+            None
+        ));
+
+        let bob_a = balance(ASSET_A, BOB);
+        let bob_b = balance(ASSET_B, BOB);
+        assert_ok!(Dex::swap_exact_out(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            9_066,
+            10_000,
+            None
+        ));
+        System::assert_last_event(
+            Event::SwappedExactOut(BOB, ASSET_A, 10_000, ASSET_B, 9_066).into(),
+        );
+
+        assert_eq!(balance(ASSET_A, BOB), bob_a - 10_000);
+        assert_eq!(balance(ASSET_B, BOB), bob_b + 9_066);
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.reserves, (110_000, 90_934));
+        assert_eq!(balance(ASSET_A, pool_account()), 110_000);
+        assert_eq!(balance(ASSET_B, pool_account()), 90_934);
+    });
+}
+
+#[test]
+fn swap_exact_out_rounds_in_favour_of_the_pool() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            100_000,
+            300_000,
+            0,
+            0,
+            None
+        ));
+
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        let k = pool.reserves.0 * pool.reserves.1;
+        for amount_out in [1, 7, 1_234, 50_000] {
+            assert_ok!(Dex::swap_exact_out(
+                RuntimeOrigin::signed(BOB),
+                ASSET_B,
+                ASSET_A,
+                amount_out,
+                u128::MAX,
+                None
+            ));
+        }
+
+        // Every swap paid at least what the constant product requires
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert!(pool.reserves.0 * pool.reserves.1 >= k);
+    });
+}
+
+#[test]
+fn swap_exact_out_respects_maximum_amount_in() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            100_000,
+            100_000,
+            0,
+            0,
+            None
+        ));
+
+        assert_noop!(
+            Dex::swap_exact_out(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                9_066,
+                9_999,
+                None
+            ),
+            Error::<Test>::ExcessiveAmountIn
+        );
+        assert_noop!(
+            Dex::swap_exact_out(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                100_000,
+                u128::MAX,
+                None
+            ),
+            Error::<Test>::InsufficientReserves
+        );
+    });
+}
+
+// Creates the A/B and B/C pools, each holding 100_000 of both assets
+fn setup_path_pools() {
+    setup_pool();
+    create_asset(ASSET_C);
+    for who in [ALICE, BOB] {
+        mint_asset(ASSET_C, who, 1_000_000);
+    }
+    assert_ok!(Dex::create_liquidity_pool(
+        RuntimeOrigin::signed(ALICE),
+        ASSET_B,
+        ASSET_C,
+        SWAP_FEE
+    ));
+    for (asset_a, asset_b) in [(ASSET_A, ASSET_B), (ASSET_B, ASSET_C)] {
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            asset_a,
+            asset_b,
+            100_000,
+            100_000,
+            0,
+            0,
+            None
+        ));
+    }
+}
+
+fn path(assets: Vec<AssetKind>) -> BoundedVec<AssetKind, ConstU32<4>> {
+    BoundedVec::try_from(assets).unwrap()
+}
+
+#[test]
+fn swap_exact_in_path_chains_pools() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_path_pools();
+        let bob_a = balance(ASSET_A, BOB);
+        let bob_b = balance(ASSET_B, BOB);
+        let bob_c = balance(ASSET_C, BOB);
+
+        assert_ok!(Dex::swap_exact_in_path(
+            RuntimeOrigin::signed(BOB),
+            path(vec![ASSET_A, ASSET_B, ASSET_C]),
+            10_000,
+            8_289,
+            None
+        ));
+        System::assert_last_event(
+            Event::SwappedAlongPath(
+                BOB,
+                path(vec![ASSET_A, ASSET_B, ASSET_C]),
+                BoundedVec::try_from(vec![10_000, 9_066, 8_289]).unwrap(),
+            )
+            .into(),
+        );
+
+        assert_eq!(balance(ASSET_A, BOB), bob_a - 10_000);
+        assert_eq!(balance(ASSET_B, BOB), bob_b);
+        assert_eq!(balance(ASSET_C, BOB), bob_c + 8_289);
+
+        let ab_pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        let bc_pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_B, ASSET_C)).unwrap();
+        assert_eq!(ab_pool.reserves, (110_000, 90_934));
+        assert_eq!(bc_pool.reserves, (109_066, 91_711));
+        let bc_account = Dex::pool_account_id(&AssetPair::new(ASSET_B, ASSET_C));
+        assert_eq!(balance(ASSET_B, pool_account()), 90_934);
+        assert_eq!(balance(ASSET_B, bc_account), 109_066);
+        assert_eq!(balance(ASSET_C, bc_account), 91_711);
+    });
+}
+
+#[test]
+fn swap_exact_out_path_chains_pools() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_path_pools();
+        let bob_a = balance(ASSET_A, BOB);
+        let bob_c = balance(ASSET_C, BOB);
+
+        assert_ok!(Dex::swap_exact_out_path(
+            RuntimeOrigin::signed(BOB),
+            path(vec![ASSET_A, ASSET_B, ASSET_C]),
+            8_289,
+            10_003,
+            None
+        ));
+        System::assert_last_event(
+            Event::SwappedAlongPath(
+                BOB,
+                path(vec![ASSET_A, ASSET_B, ASSET_C]),
+                BoundedVec::try_from(vec![10_003, 9_067, 8_289]).unwrap(),
+            )
+            .into(),
+        );
+
+        assert_eq!(balance(ASSET_A, BOB), bob_a - 10_003);
+        assert_eq!(balance(ASSET_C, BOB), bob_c + 8_289);
+        let bc_pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_B, ASSET_C)).unwrap();
+        assert_eq!(bc_pool.reserves, (109_067, 91_711));
+    });
+}
+
+#[test]
+fn path_swaps_check_slippage_at_the_ends() {
+    new_test_ext().execute_with(|| {
+        setup_path_pools();
+        assert_noop!(
+            Dex::swap_exact_in_path(
+                RuntimeOrigin::signed(BOB),
+                path(vec![ASSET_A, ASSET_B, ASSET_C]),
+                10_000,
+                8_290,
+                None
+            ),
+            Error::<Test>::InsufficientAmountOut
+        );
+        assert_noop!(
+            Dex::swap_exact_out_path(
+                RuntimeOrigin::signed(BOB),
+                path(vec![ASSET_A, ASSET_B, ASSET_C]),
+                8_289,
+                10_002,
+                None
+            ),
+            Error::<Test>::ExcessiveAmountIn
+        );
+    });
+}
+
+#[test]
+fn path_swaps_reject_invalid_paths() {
+    new_test_ext().execute_with(|| {
+        setup_path_pools();
+        assert_noop!(
+            Dex::swap_exact_in_path(
+                RuntimeOrigin::signed(BOB),
+                path(vec![ASSET_A]),
+                1_000,
+                0,
+                None
+            ),
+            Error::<Test>::InvalidPath
+        );
+        assert_noop!(
+            Dex::swap_exact_in_path(
+                RuntimeOrigin::signed(BOB),
+                path(vec![ASSET_A, ASSET_B, ASSET_A]),
+                1_000,
+                0,
+                None
+            ),
+            Error::<Test>::InvalidPath
+        );
+        assert_noop!(
+            Dex::swap_exact_out_path(
+                RuntimeOrigin::signed(BOB),
+                path(vec![ASSET_A, ASSET_C]),
+                1_000,
+                u128::MAX,
+                None
+            ),
+            Error::<Test>::LiquidityPoolNotFound
+        );
+    });
+}
+
+#[test]
+fn native_currency_pools_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_asset(ASSET_A);
+        mint_asset(ASSET_A, ALICE, 1_000_000);
+        assert_ok!(Dex::create_liquidity_pool(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            NATIVE,
+            SWAP_FEE
+        ));
+        System::assert_last_event(
+            Event::LiquidityPoolCreated(ALICE, NATIVE, ASSET_A, LP_TOKEN).into(),
+        );
+        let native_pool_account = Dex::pool_account_id(&AssetPair::new(NATIVE, ASSET_A));
+
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            NATIVE,
+            ASSET_A,
+            100_000,
+            100_000,
+            0,
+            0,
+            None
+        ));
+        // Alice's creation deposit is held
+        let deposit = PoolCreationDeposit::get();
+        assert_eq!(Balances::free_balance(ALICE), 900_000 - deposit);
+        assert_eq!(Balances::free_balance(native_pool_account), 100_000);
+        assert_eq!(balance(NATIVE, native_pool_account), 100_000);
+
+        // Sell native currency for asset A
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            NATIVE,
+            ASSET_A,
+            10_000,
+            0,
+            None
+        ));
+        assert_eq!(Balances::free_balance(BOB), 990_000);
+        assert_eq!(balance(ASSET_A, BOB), 9_066);
+        assert_eq!(Balances::free_balance(native_pool_account), 110_000);
+
+        // And withdraw both sides of the pool
+        assert_ok!(Dex::burn_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            NATIVE,
+            ASSET_A,
+            99_000,
+            0,
+            0,
+            None,
+            None
+        ));
+        assert_eq!(Balances::free_balance(native_pool_account), 1_100);
+        assert_eq!(Balances::free_balance(ALICE), 900_000 - deposit + 108_900);
+    });
+}
+
+#[test]
+fn native_balance_conversion_round_trips() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(NativeOrAssets::<Test>::to_asset_balance(1_234), Ok(1_234));
+        assert_eq!(NativeOrAssets::<Test>::to_native_balance(1_234), Ok(1_234));
+    });
+}
+
+// Sends `amount` of `asset` straight to the A/B pool account, bypassing the pallet
+fn donate(asset: AssetKind, amount: Balance) {
+    assert_ok!(NativeOrAssets::<Test>::transfer(
+        asset,
+        &BOB,
+        &pool_account(),
+        amount,
+        Preservation::Expendable
+    ));
+}
+
+#[test]
+fn reserve_drift_reports_excess_holdings() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+        assert_eq!(
+            Dex::reserve_drift(ASSET_A, ASSET_B),
+            Ok(ReserveDrift {
+                excess: (0, 0),
+                shortfall: (0, 0)
+            })
+        );
+
+        donate(ASSET_B, 500);
+        assert_eq!(
+            Dex::reserve_drift(ASSET_B, ASSET_A),
+            Ok(ReserveDrift {
+                excess: (0, 500),
+                shortfall: (0, 0)
+            })
+        );
+        assert_eq!(
+            Dex::reserve_drift(ASSET_A, ASSET_C),
+            Err(Error::<Test>::LiquidityPoolNotFound.into())
+        );
+    });
+}
+
+#[test]
+fn sync_resets_reserves_to_holdings() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+        donate(ASSET_A, 1_000);
+
+        assert_ok!(Dex::sync(RuntimeOrigin::signed(CHARLIE), ASSET_A, ASSET_B));
+        System::assert_last_event(Event::ReservesSynced(ASSET_A, ASSET_B, 11_000, 10_000).into());
+
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.reserves, (11_000, 10_000));
+        assert_eq!(Dex::reserve_drift(ASSET_A, ASSET_B).unwrap().excess, (0, 0));
+    });
+}
+
+#[test]
+fn skim_sends_out_the_excess() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+        donate(ASSET_A, 1_000);
+        donate(ASSET_B, 300);
+
+        assert_ok!(Dex::skim(
+            RuntimeOrigin::signed(CHARLIE),
+            ASSET_A,
+            ASSET_B,
+            CHARLIE
+        ));
+        System::assert_last_event(Event::Skimmed(ASSET_A, ASSET_B, CHARLIE, 1_000, 300).into());
+
+        assert_eq!(balance(ASSET_A, CHARLIE), 1_000);
+        assert_eq!(balance(ASSET_B, CHARLIE), 300);
+        assert_eq!(balance(ASSET_A, pool_account()), 10_000);
+        assert_eq!(balance(ASSET_B, pool_account()), 10_000);
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.reserves, (10_000, 10_000));
+    });
+}
+
+#[test]
+fn sync_and_skim_respect_the_pool_status() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+        donate(ASSET_A, 1_000);
+        assert_ok!(Dex::set_pool_status(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            ASSET_B,
+            PoolStatus::FullyPaused
+        ));
+        assert_noop!(
+            Dex::sync(RuntimeOrigin::signed(CHARLIE), ASSET_A, ASSET_B),
+            Error::<Test>::PoolPaused
+        );
+        assert_noop!(
+            Dex::skim(RuntimeOrigin::signed(CHARLIE), ASSET_A, ASSET_B, CHARLIE),
+            Error::<Test>::PoolPaused
+        );
+
+        // Withdrawing pools can still be skimmed, but not synced
+        assert_ok!(Dex::set_pool_status(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            ASSET_B,
+            PoolStatus::WithdrawOnly
+        ));
+        assert_noop!(
+            Dex::sync(RuntimeOrigin::signed(CHARLIE), ASSET_A, ASSET_B),
+            Error::<Test>::PoolPaused
+        );
+        assert_ok!(Dex::skim(
+            RuntimeOrigin::signed(CHARLIE),
+            ASSET_A,
+            ASSET_B,
+            CHARLIE
+        ));
+        assert_eq!(balance(ASSET_A, CHARLIE), 1_000);
+    });
+}
+
+#[test]
+fn concentrated_pools_cannot_be_synced_or_skimmed() {
+    new_test_ext().execute_with(|| {
+        setup_concentrated_pool();
+        donate(ASSET_A, 1_000);
+        assert_noop!(
+            Dex::sync(RuntimeOrigin::signed(CHARLIE), ASSET_A, ASSET_B),
+            Error::<Test>::ConcentratedLiquidityPool
+        );
+        assert_noop!(
+            Dex::skim(RuntimeOrigin::signed(CHARLIE), ASSET_A, ASSET_B, CHARLIE),
+            Error::<Test>::ConcentratedLiquidityPool
+        );
+    });
+}
+
+#[test]
+fn quotes_match_swap_execution() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            100_000,
+            300_000,
+            0,
+            0,
+            None
+        ));
+        assert_ok!(Dex::set_protocol_fee(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            ASSET_B,
+            Permill::from_percent(20)
+        ));
+
+        let quoted_out = Dex::quote_exact_in(ASSET_A, ASSET_B, 10_000).unwrap();
+        let bob_b = balance(ASSET_B, BOB);
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            0,
+            None
+        ));
+        assert_eq!(balance(ASSET_B, BOB), bob_b + quoted_out);
+
+        let quoted_in = Dex::quote_exact_out(ASSET_B, ASSET_A, 7_777).unwrap();
+        let bob_b = balance(ASSET_B, BOB);
+        assert_ok!(Dex::swap_exact_out(
+            RuntimeOrigin::signed(BOB),
+            ASSET_B,
+            ASSET_A,
+            7_777,
+            u128::MAX,
+            None
+        ));
+        assert_eq!(balance(ASSET_B, BOB), bob_b - quoted_in);
+    });
+}
+
+#[test]
+fn quotes_fail_where_swaps_would() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_eq!(Dex::quote_exact_in(ASSET_A, ASSET_B, 1_000), None);
+        assert_eq!(Dex::quote_exact_in(ASSET_A, ASSET_C, 1_000), None);
+
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+        assert_eq!(Dex::quote_exact_out(ASSET_A, ASSET_B, 10_000), None);
+        assert_eq!(Dex::quote_exact_in(ASSET_A, ASSET_A, 1_000), None);
+
+        // Paused pools and unlisted assets cannot be quoted either
+        assert_ok!(Dex::set_pool_status(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            ASSET_B,
+            PoolStatus::SwapsPaused
+        ));
+        assert_eq!(Dex::quote_exact_in(ASSET_A, ASSET_B, 1_000), None);
+        assert_eq!(Dex::quote_exact_out(ASSET_A, ASSET_B, 1_000), None);
+        assert_ok!(Dex::set_pool_status(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            ASSET_B,
+            PoolStatus::Active
+        ));
+        assert!(Dex::quote_exact_in(ASSET_A, ASSET_B, 1_000).is_some());
+
+        assert_ok!(Dex::set_asset_listing(
+            RuntimeOrigin::root(),
+            ASSET_B,
+            Some(AssetListing::Denied)
+        ));
+        assert_eq!(Dex::quote_exact_in(ASSET_A, ASSET_B, 1_000), None);
+        assert_eq!(Dex::quote_exact_out(ASSET_B, ASSET_A, 1_000), None);
+    });
+}
+
+#[test]
+fn pool_queries_return_pool_state() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            20_000,
+            0,
+            0,
+            None
+        ));
+
+        assert_eq!(Dex::get_reserves(ASSET_B, ASSET_A), Some((10_000, 20_000)));
+        assert_eq!(Dex::lp_token_for_pair(ASSET_B, ASSET_A), Some(LP_TOKEN));
+        assert_eq!(
+            Dex::get_pool(ASSET_A, ASSET_B),
+            LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B))
+        );
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_C), None);
+        assert_eq!(Dex::lp_token_for_pair(ASSET_A, ASSET_C), None);
+    });
+}
+
+#[test]
+fn calls_fail_after_their_deadline() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(10);
+        setup_pool();
+        assert_noop!(
+            Dex::mint_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                ASSET_A,
+                ASSET_B,
+                10_000,
+                10_000,
+                0,
+                0,
+                Some(9)
+            ),
+            Error::<Test>::DeadlineExpired
+        );
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            Some(10)
+        ));
+
+        assert_noop!(
+            Dex::burn_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                ASSET_A,
+                ASSET_B,
+                1_000,
+                0,
+                0,
+                None,
+                Some(9)
+            ),
+            Error::<Test>::DeadlineExpired
+        );
+        assert_noop!(
+            Dex::swap(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                1_000,
+                0,
+                Some(9)
+            ),
+            Error::<Test>::DeadlineExpired
+        );
+        assert_noop!(
+            Dex::swap_exact_out(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                1_000,
+                u128::MAX,
+                Some(9)
+            ),
+            Error::<Test>::DeadlineExpired
+        );
+    });
+}
+
+#[test]
+fn path_swaps_fail_after_their_deadline() {
+    new_test_ext().execute_with(|| {
+        setup_path_pools();
+        System::set_block_number(5);
+
+        assert_noop!(
+            Dex::swap_exact_in_path(
+                RuntimeOrigin::signed(BOB),
+                path(vec![ASSET_A, ASSET_B, ASSET_C]),
+                1_000,
+                0,
+                Some(4)
+            ),
+            Error::<Test>::DeadlineExpired
+        );
+        assert_noop!(
+            Dex::swap_exact_out_path(
+                RuntimeOrigin::signed(BOB),
+                path(vec![ASSET_A, ASSET_B, ASSET_C]),
+                1_000,
+                u128::MAX,
+                Some(4)
+            ),
+            Error::<Test>::DeadlineExpired
+        );
+        assert_ok!(Dex::swap_exact_in_path(
+            RuntimeOrigin::signed(BOB),
+            path(vec![ASSET_A, ASSET_B, ASSET_C]),
+            1_000,
+            0,
+            Some(5)
+        ));
+    });
+}
+
+#[test]
+fn destroy_pool_requires_all_liquidity_burned() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            40_000,
+            0,
+            0,
+            None
+        ));
+
+        assert_noop!(
+            Dex::destroy_pool(RuntimeOrigin::signed(BOB), ASSET_A, ASSET_B),
+            Error::<Test>::LiquidityPoolNotEmpty
+        );
+
+        assert_ok!(Dex::burn_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            19_000,
+            0,
+            0,
+            None,
+            None
+        ));
+        assert_ok!(Dex::destroy_pool(
+            RuntimeOrigin::signed(BOB),
+            ASSET_B,
+            ASSET_A
+        ));
+        System::assert_last_event(Event::LiquidityPoolDestroyed(ASSET_A, ASSET_B, LP_TOKEN).into());
+
+        assert!(!LiquidityPools::<Test>::contains_key(AssetPair::new(
+            ASSET_A, ASSET_B
+        )));
+        assert_eq!(LiquidityTokens::<Test>::get(LP_TOKEN), None);
+        assert_noop!(
+            Dex::destroy_pool(RuntimeOrigin::signed(BOB), ASSET_A, ASSET_B),
+            Error::<Test>::LiquidityPoolNotFound
+        );
+    });
+}
+
+#[test]
+fn destroy_pool_sweeps_the_pool_account() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            40_000,
+            0,
+            0,
+            None
+        ));
+        assert_ok!(Dex::burn_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            19_000,
+            0,
+            0,
+            None,
+            None
+        ));
+        // The locked minimum liquidity is backed by what is left in the pool account
+        assert_eq!(balance(ASSET_A, pool_account()), 500);
+        assert_eq!(balance(ASSET_B, pool_account()), 2_000);
+
+        assert_ok!(Dex::destroy_pool(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B
+        ));
+
+        System::assert_has_event(
+            Event::LiquidityPoolSwept(ASSET_A, ASSET_B, TREASURY, 500, 2_000).into(),
+        );
+        assert_eq!(balance(ASSET_A, TREASURY), 500);
+        assert_eq!(balance(ASSET_B, TREASURY), 2_000);
+        assert_eq!(balance(ASSET_A, pool_account()), 0);
+        assert_eq!(balance(ASSET_B, pool_account()), 0);
+        assert_eq!(System::providers(&pool_account()), 0);
+
+        // The liquidity token can be fully destroyed through the assets pallet
+        assert_ok!(Assets::destroy_accounts(
+            RuntimeOrigin::signed(CHARLIE),
+            LP_TOKEN.into()
+        ));
+        assert_ok!(Assets::destroy_approvals(
+            RuntimeOrigin::signed(CHARLIE),
+            LP_TOKEN.into()
+        ));
+        assert_ok!(Assets::finish_destroy(
+            RuntimeOrigin::signed(CHARLIE),
+            LP_TOKEN.into()
+        ));
+        assert!(!<Assets as Inspect<u64>>::asset_exists(LP_TOKEN));
+
+        // The pair can be listed again with a new liquidity token
+        assert_ok!(Dex::create_liquidity_pool(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            SWAP_FEE
+        ));
+        assert_eq!(Dex::lp_token_for_pair(ASSET_A, ASSET_B), Some(LP_TOKEN + 1));
+    });
+}
+
+#[test]
+fn destroy_pool_never_sweeps_outstanding_liquidity() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+
+        // Not even the admin origin can take the assets of liquidity providers
+        assert_noop!(
+            Dex::destroy_pool(RuntimeOrigin::root(), ASSET_A, ASSET_B),
+            Error::<Test>::LiquidityPoolNotEmpty
+        );
+        assert_noop!(
+            Dex::destroy_pool(RuntimeOrigin::none(), ASSET_A, ASSET_B),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Dex::burn_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            9_000,
+            0,
+            0,
+            None,
+            None
+        ));
+        assert_ok!(Dex::destroy_pool(RuntimeOrigin::root(), ASSET_A, ASSET_B));
+        assert!(!LiquidityPools::<Test>::contains_key(AssetPair::new(
+            ASSET_A, ASSET_B
+        )));
+        assert_eq!(balance(ASSET_A, TREASURY), 1_000);
+        assert_eq!(balance(ASSET_B, TREASURY), 1_000);
+    });
+}
+
+#[test]
+fn pool_creation_holds_a_deposit() {
+    new_test_ext().execute_with(|| {
+        let deposit = PoolCreationDeposit::get();
+        setup_pool();
+
+        let hold_reason = RuntimeHoldReason::Dex(HoldReason::PoolCreation);
+        assert_eq!(Balances::balance_on_hold(&hold_reason, &ALICE), deposit);
+        assert_eq!(Balances::free_balance(ALICE), 1_000_000 - deposit);
+
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.depositor, ALICE);
+        assert_eq!(pool.deposit, deposit);
+    });
+}
+
+#[test]
+fn pool_creation_requires_the_deposit() {
+    new_test_ext().execute_with(|| {
+        create_asset(ASSET_A);
+        create_asset(ASSET_B);
+        let pauper = 42;
+        assert_ok!(Balances::force_set_balance(
+            RuntimeOrigin::root(),
+            pauper,
+            PoolCreationDeposit::get() - 1
+        ));
+
+        assert!(Dex::create_liquidity_pool(
+            RuntimeOrigin::signed(pauper),
+            ASSET_A,
+            ASSET_B,
+            SWAP_FEE
+        )
+        .is_err());
+        assert!(!LiquidityPools::<Test>::contains_key(AssetPair::new(
+            ASSET_A, ASSET_B
+        )));
+    });
+}
+
+#[test]
+fn destroy_pool_releases_the_deposit() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+
+        // Only the depositor can destroy a pool that never held liquidity
+        assert_noop!(
+            Dex::destroy_pool(RuntimeOrigin::signed(BOB), ASSET_A, ASSET_B),
+            Error::<Test>::NotPoolDepositor
+        );
+        assert_ok!(Dex::destroy_pool(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B
+        ));
+
+        let hold_reason = RuntimeHoldReason::Dex(HoldReason::PoolCreation);
+        assert_eq!(Balances::balance_on_hold(&hold_reason, &ALICE), 0);
+        assert_eq!(Balances::free_balance(ALICE), 1_000_000);
+        assert_eq!(Balances::free_balance(BOB), 1_000_000);
+    });
+}
+
+#[test]
+fn pool_creation_requires_the_create_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Dex::create_liquidity_pool(RuntimeOrigin::none(), ASSET_A, ASSET_B, SWAP_FEE),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Dex::create_liquidity_pool(RuntimeOrigin::root(), ASSET_A, ASSET_B, SWAP_FEE),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn asset_listings_are_managed_by_the_admin() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            Dex::set_asset_listing(
+                RuntimeOrigin::signed(ALICE),
+                ASSET_A,
+                Some(AssetListing::Allowed)
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Dex::set_allowlist_enforced(RuntimeOrigin::signed(ALICE), true),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Dex::set_asset_listing(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            Some(AssetListing::Allowed)
+        ));
+        System::assert_last_event(
+            Event::AssetListingUpdated(ASSET_A, Some(AssetListing::Allowed)).into(),
+        );
+        assert_eq!(
+            AssetListings::<Test>::get(ASSET_A),
+            Some(AssetListing::Allowed)
+        );
+
+        assert_ok!(Dex::set_asset_listing(RuntimeOrigin::root(), ASSET_A, None));
+        System::assert_last_event(Event::AssetListingUpdated(ASSET_A, None).into());
+        assert_eq!(AssetListings::<Test>::get(ASSET_A), None);
+
+        assert_ok!(Dex::set_allowlist_enforced(RuntimeOrigin::root(), true));
+        System::assert_last_event(Event::AllowlistEnforcementUpdated(true).into());
+        assert!(AllowlistEnforced::<Test>::get());
+    });
+}
+
+#[test]
+fn enforced_allowlist_restricts_pool_creation() {
+    new_test_ext().execute_with(|| {
+        create_asset(ASSET_A);
+        create_asset(ASSET_B);
+        assert_ok!(Dex::set_allowlist_enforced(RuntimeOrigin::root(), true));
+        assert_ok!(Dex::set_asset_listing(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            Some(AssetListing::Allowed)
+        ));
+
+        assert_noop!(
+            Dex::create_liquidity_pool(RuntimeOrigin::signed(ALICE), ASSET_A, ASSET_B, SWAP_FEE),
+            Error::<Test>::AssetNotAllowed
+        );
+
+        assert_ok!(Dex::set_asset_listing(
+            RuntimeOrigin::root(),
+            ASSET_B,
+            Some(AssetListing::Allowed)
+        ));
+        assert_ok!(Dex::create_liquidity_pool(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            SWAP_FEE
+        ));
+    });
+}
+
+#[test]
+fn denied_assets_cannot_be_traded() {
+    new_test_ext().execute_with(|| {
+        setup_path_pools();
+        assert_ok!(Dex::set_asset_listing(
+            RuntimeOrigin::root(),
+            ASSET_B,
+            Some(AssetListing::Denied)
+        ));
+
+        assert_noop!(
+            Dex::create_liquidity_pool(RuntimeOrigin::signed(ALICE), ASSET_B, NATIVE, SWAP_FEE),
+            Error::<Test>::AssetDenied
+        );
+        assert_noop!(
+            Dex::swap(RuntimeOrigin::signed(BOB), ASSET_A, ASSET_B, 1_000, 0, None),
+            Error::<Test>::AssetDenied
+        );
+        assert_noop!(
+            Dex::swap_exact_out(
+                RuntimeOrigin::signed(BOB),
+                ASSET_B,
+                ASSET_A,
+                1_000,
+                u128::MAX,
+                None
+            ),
+            Error::<Test>::AssetDenied
+        );
+        // Denied assets in the middle of a path are rejected too
+        assert_noop!(
+            Dex::swap_exact_in_path(
+                RuntimeOrigin::signed(BOB),
+                path(vec![ASSET_A, ASSET_B, ASSET_C]),
+                1_000,
+                0,
+                None
+            ),
+            Error::<Test>::AssetDenied
+        );
+
+        // Liquidity can still be withdrawn
+        assert_ok!(Dex::burn_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            1_000,
+            0,
+            0,
+            None,
+            None
+        ));
+    });
+}
+
+const ONE: u128 = 1_000_000_000_000_000_000;
+
+#[test]
+fn price_cumulative_updates_on_first_touch_per_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            40_000,
+            0,
+            0,
+            None
+        ));
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.price_cumulative, (0, 0));
+        assert_eq!(pool.last_update, 1);
+
+        // The prices held over the last 10 blocks are accumulated before the swap
+        System::set_block_number(11);
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            1_000,
+            0,
+            None
+        ));
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.price_cumulative, (4 * ONE * 10, ONE / 4 * 10));
+        assert_eq!(pool.last_update, 11);
+
+        // Later touches in the same block leave the accumulators alone
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            1_000,
+            0,
+            None
+        ));
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.price_cumulative, (4 * ONE * 10, ONE / 4 * 10));
+    });
+}
+
+#[test]
+fn twap_averages_prices_over_the_window() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+
+        System::set_block_number(11);
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            1_000,
+            0,
+            None
+        ));
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        let price_a = FixedU128::saturating_from_rational(pool.reserves.1, pool.reserves.0);
+        let price_b = FixedU128::saturating_from_rational(pool.reserves.0, pool.reserves.1);
+
+        System::set_block_number(21);
+        assert_eq!(Dex::twap(ASSET_A, ASSET_B, 10), Some(price_a));
+        assert_eq!(Dex::twap(ASSET_B, ASSET_A, 10), Some(price_b));
+        assert_eq!(
+            Dex::twap(ASSET_A, ASSET_B, 20),
+            Some(FixedU128::from_inner(
+                (ONE * 10 + price_a.into_inner() * 10) / 20
+            ))
+        );
+        assert_eq!(
+            Dex::twap(ASSET_A, ASSET_B, 15),
+            Some(FixedU128::from_inner(
+                (ONE * 5 + price_a.into_inner() * 10) / 15
+            ))
+        );
+
+        // Windows must be non-empty and covered by the pool's observations
+        assert_eq!(Dex::twap(ASSET_A, ASSET_B, 0), None);
+        assert_eq!(Dex::twap(ASSET_A, ASSET_B, 21), None);
+        assert_eq!(Dex::twap(ASSET_A, ASSET_C, 10), None);
+    });
+}
+
+#[test]
+fn price_observations_keep_the_latest_blocks() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            10_000,
+            10_000,
+            0,
+            0,
+            None
+        ));
+        for block in 2..=12 {
+            System::set_block_number(block);
+            assert_ok!(Dex::swap(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                100,
+                0,
+                None
+            ));
+        }
+
+        let trading_pair = AssetPair::new(ASSET_A, ASSET_B);
+        let blocks: Vec<u64> = PriceObservations::<Test>::get(&trading_pair)
+            .ordered()
+            .map(|observation| observation.block_number)
+            .collect();
+        assert_eq!(blocks, (5..=12).collect::<Vec<u64>>());
+
+        assert!(Dex::twap(ASSET_A, ASSET_B, 7).is_some());
+        assert_eq!(Dex::twap(ASSET_A, ASSET_B, 8), None);
+
+        // Destroying the pool drops its observations
+        assert_ok!(burn(ALICE, 9_000));
+        assert_ok!(Dex::destroy_pool(RuntimeOrigin::root(), ASSET_A, ASSET_B));
+        assert_eq!(
+            PriceObservations::<Test>::get(&trading_pair)
+                .ordered()
+                .count(),
+            0
+        );
+    });
+}
+
+fn mint(who: u64, amount: Balance) -> DispatchResult {
+    Dex::mint_liquidity(
+        RuntimeOrigin::signed(who),
+        ASSET_A,
+        ASSET_B,
+        amount,
+        amount,
+        0,
+        0,
+        None,
+    )
+}
+
+fn burn(who: u64, liquidity: Balance) -> DispatchResult {
+    Dex::burn_liquidity(
+        RuntimeOrigin::signed(who),
+        ASSET_A,
+        ASSET_B,
+        liquidity,
+        0,
+        0,
+        None,
+        None,
+    )
+}
+
+fn swap(who: u64, amount_in: Balance) -> DispatchResultWithPostInfo {
+    Dex::swap(
+        RuntimeOrigin::signed(who),
+        ASSET_A,
+        ASSET_B,
+        amount_in,
+        0,
+        None,
+    )
+}
+
+#[test]
+fn statuses_are_set_by_the_pause_origin() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+
+        assert_noop!(
+            Dex::set_global_status(RuntimeOrigin::signed(ALICE), PoolStatus::FullyPaused),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Dex::set_pool_status(
+                RuntimeOrigin::signed(ALICE),
+                ASSET_A,
+                ASSET_B,
+                PoolStatus::FullyPaused
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Dex::set_pool_status(
+                RuntimeOrigin::root(),
+                ASSET_A,
+                ASSET_C,
+                PoolStatus::FullyPaused
+            ),
+            Error::<Test>::LiquidityPoolNotFound
+        );
+
+        assert_ok!(Dex::set_global_status(
+            RuntimeOrigin::root(),
+            PoolStatus::SwapsPaused
+        ));
+        System::assert_last_event(Event::GlobalStatusUpdated(PoolStatus::SwapsPaused).into());
+        assert_eq!(GlobalStatus::<Test>::get(), PoolStatus::SwapsPaused);
+
+        assert_ok!(Dex::set_pool_status(
+            RuntimeOrigin::root(),
+            ASSET_B,
+            ASSET_A,
+            PoolStatus::WithdrawOnly
+        ));
+        System::assert_last_event(
+            Event::PoolStatusUpdated(ASSET_A, ASSET_B, PoolStatus::WithdrawOnly).into(),
+        );
+        let pool = LiquidityPools::<Test>::get(AssetPair::new(ASSET_A, ASSET_B)).unwrap();
+        assert_eq!(pool.status, PoolStatus::WithdrawOnly);
+    });
+}
+
+#[test]
+fn pool_status_restricts_operations() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_ok!(mint(ALICE, 10_000));
+        let set_status = |status| {
+            assert_ok!(Dex::set_pool_status(
+                RuntimeOrigin::root(),
+                ASSET_A,
+                ASSET_B,
+                status
+            ));
+        };
+
+        set_status(PoolStatus::SwapsPaused);
+        assert_noop!(swap(BOB, 1_000), Error::<Test>::PoolPaused);
+        assert_ok!(mint(BOB, 1_000));
+        assert_ok!(burn(BOB, 100));
+
+        set_status(PoolStatus::WithdrawOnly);
+        assert_noop!(swap(BOB, 1_000), Error::<Test>::PoolPaused);
+        assert_noop!(mint(BOB, 1_000), Error::<Test>::PoolPaused);
+        assert_ok!(burn(BOB, 100));
+
+        set_status(PoolStatus::FullyPaused);
+        assert_noop!(swap(BOB, 1_000), Error::<Test>::PoolPaused);
+        assert_noop!(mint(BOB, 1_000), Error::<Test>::PoolPaused);
+        assert_noop!(burn(BOB, 100), Error::<Test>::PoolPaused);
+        assert_noop!(
+            Dex::swap_exact_out(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                100,
+                u128::MAX,
+                None
+            ),
+            Error::<Test>::PoolPaused
+        );
+
+        set_status(PoolStatus::Active);
+        assert_ok!(swap(BOB, 1_000));
+    });
+}
+
+#[test]
+fn global_status_applies_to_every_pool() {
+    new_test_ext().execute_with(|| {
+        setup_path_pools();
+        assert_ok!(Dex::set_global_status(
+            RuntimeOrigin::root(),
+            PoolStatus::SwapsPaused
+        ));
+
+        assert_noop!(swap(BOB, 1_000), Error::<Test>::PoolPaused);
+        assert_noop!(
+            Dex::swap_exact_in_path(
+                RuntimeOrigin::signed(BOB),
+                path(vec![ASSET_A, ASSET_B, ASSET_C]),
+                1_000,
+                0,
+                None
+            ),
+            Error::<Test>::PoolPaused
+        );
+        assert_ok!(mint(BOB, 1_000));
+
+        // A pool's own status can only restrict further
+        assert_ok!(Dex::set_pool_status(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            ASSET_B,
+            PoolStatus::WithdrawOnly
+        ));
+        assert_noop!(mint(BOB, 1_000), Error::<Test>::PoolPaused);
+
+        assert_ok!(Dex::set_global_status(
+            RuntimeOrigin::root(),
+            PoolStatus::Active
+        ));
+        assert_noop!(swap(BOB, 1_000), Error::<Test>::PoolPaused);
+        assert_ok!(Dex::swap_exact_in_path(
+            RuntimeOrigin::signed(BOB),
+            path(vec![ASSET_B, ASSET_C]),
+            1_000,
+            0,
+            None
+        ));
+    });
+}
+
+// Creates the A/B pool as a StableSwap pool and funds Alice and Bob with both assets
+fn setup_stable_pool(amplification: u32) {
+    create_asset(ASSET_A);
+    create_asset(ASSET_B);
+    for who in [ALICE, BOB] {
+        mint_asset(ASSET_A, who, 1_000_000);
+        mint_asset(ASSET_B, who, 1_000_000);
+    }
+    assert_ok!(Dex::create_stable_pool(
+        RuntimeOrigin::signed(ALICE),
+        ASSET_A,
+        ASSET_B,
+        SWAP_FEE,
+        amplification
+    ));
+}
+
+fn amplification_at(block: u64) -> u32 {
+    match Dex::get_pool(ASSET_A, ASSET_B).unwrap().kind {
+        PoolKind::StableSwap(amplification) => amplification.at(block),
+        _ => panic!("the A/B pool is not a StableSwap pool"),
+    }
+}
+
+#[test]
+fn stable_swap_pool_swaps_close_to_one_to_one() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_stable_pool(100);
+        assert_ok!(mint(ALICE, 100_000));
+
+        // The first deposit mints the invariant, the sum of balanced reserves
+        let liquidity_pool = Dex::get_pool(ASSET_A, ASSET_B).unwrap();
+        assert_eq!(liquidity_pool.total_liquidity, 200_000);
+        assert_eq!(Assets::balance(LP_TOKEN, ALICE), 199_000);
+
+        // The constant product would only pay out 9_066 for the same amount after fees
+        assert_eq!(Dex::quote_exact_in(ASSET_A, ASSET_B, 10_000), Some(9_965));
+        assert_ok!(swap(BOB, 10_000));
+        assert_eq!(balance(ASSET_B, BOB), 1_009_965);
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((110_000, 90_035)));
+        System::assert_last_event(Event::Swapped(BOB, ASSET_A, 10_000, ASSET_B, 9_965).into());
+
+        // The invariant never decreases through a swap
+        let liquidity_pool = Dex::get_pool(ASSET_A, ASSET_B).unwrap();
+        assert!(liquidity_pool.invariant(liquidity_pool.reserves).unwrap() >= 200_000);
+
+        // Withdrawals stay proportional to the reserves
+        assert_ok!(burn(ALICE, 20_000));
+        assert_eq!(balance(ASSET_A, ALICE), 900_000 + 11_000);
+        assert_eq!(balance(ASSET_B, ALICE), 900_000 + 9_003);
+    });
+}
+
+#[test]
+fn stable_swap_exact_out_charges_the_quoted_amount() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_stable_pool(100);
+        assert_ok!(mint(ALICE, 100_000));
+
+        // Buying what 10_000 sells for costs no more than 10_000
+        let amount_in = Dex::quote_exact_out(ASSET_A, ASSET_B, 9_965).unwrap();
+        assert_eq!(amount_in, 10_000);
+
+        assert_noop!(
+            Dex::swap_exact_out(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                9_965,
+                amount_in - 1,
+                None
+            ),
+            Error::<Test>::ExcessiveAmountIn
+        );
+        assert_ok!(Dex::swap_exact_out(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            9_965,
+            amount_in,
+            None
+        ));
+        assert_eq!(balance(ASSET_A, BOB), 1_000_000 - amount_in);
+        assert_eq!(balance(ASSET_B, BOB), 1_009_965);
+
+        // The pool can never be drained of the asset bought
+        assert_noop!(
+            Dex::swap_exact_out(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                100_000,
+                u128::MAX,
+                None
+            ),
+            Error::<Test>::InsufficientReserves
+        );
+    });
+}
+
+#[test]
+fn amplification_ramps_linearly_until_stopped() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_stable_pool(10);
+        assert_ok!(mint(ALICE, 100_000));
+        assert_eq!(Dex::quote_exact_in(ASSET_A, ASSET_B, 10_000), Some(9_922));
+
+        // Only the admin origin can ramp, over at least the minimum duration and by at most a
+        // factor of ten
+        assert_noop!(
+            Dex::ramp_amplification(RuntimeOrigin::signed(ALICE), ASSET_A, ASSET_B, 100, 11),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Dex::ramp_amplification(RuntimeOrigin::root(), ASSET_A, ASSET_B, 100, 10),
+            Error::<Test>::InvalidAmplificationRamp
+        );
+        assert_noop!(
+            Dex::ramp_amplification(RuntimeOrigin::root(), ASSET_A, ASSET_B, 101, 11),
+            Error::<Test>::InvalidAmplificationRamp
+        );
+        assert_noop!(
+            Dex::ramp_amplification(RuntimeOrigin::root(), ASSET_A, ASSET_B, 0, 11),
+            Error::<Test>::InvalidAmplification
+        );
+
+        assert_ok!(Dex::ramp_amplification(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            ASSET_B,
+            100,
+            11
+        ));
+        System::assert_last_event(
+            Event::AmplificationRampStarted(ASSET_A, ASSET_B, 10, 100, 11).into(),
+        );
+        assert_eq!(amplification_at(6), 55);
+        assert_eq!(amplification_at(11), 100);
+
+        // Stopping keeps the coefficient reached so far
+        System::set_block_number(6);
+        assert_ok!(Dex::stop_amplification_ramp(
+            RuntimeOrigin::root(),
+            ASSET_A,
+            ASSET_B
+        ));
+        System::assert_last_event(Event::AmplificationRampStopped(ASSET_A, ASSET_B, 55).into());
+        assert_eq!(amplification_at(20), 55);
+        System::set_block_number(20);
+        assert_eq!(Dex::quote_exact_in(ASSET_A, ASSET_B, 10_000), Some(9_960));
+    });
+}
+
+#[test]
+fn stable_swap_parameters_are_validated() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_asset(ASSET_A);
+        create_asset(ASSET_B);
+        for amplification in [0, 1_000_001] {
+            assert_noop!(
+                Dex::create_stable_pool(
+                    RuntimeOrigin::signed(ALICE),
+                    ASSET_A,
+                    ASSET_B,
+                    SWAP_FEE,
+                    amplification
+                ),
+                Error::<Test>::InvalidAmplification
+            );
+        }
+
+        // Constant product pools have no amplification to ramp
+        assert_ok!(Dex::create_liquidity_pool(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_A,
+            ASSET_B,
+            SWAP_FEE
+        ));
+        assert_noop!(
+            Dex::ramp_amplification(RuntimeOrigin::root(), ASSET_A, ASSET_B, 100, 11),
+            Error::<Test>::NotStableSwapPool
+        );
+        assert_noop!(
+            Dex::stop_amplification_ramp(RuntimeOrigin::root(), ASSET_A, ASSET_B),
+            Error::<Test>::NotStableSwapPool
+        );
+    });
+}
+
+// Creates the A/B pool as an 80/20 weighted pool and funds Alice and Bob with both assets
+fn setup_weighted_pool() {
+    create_asset(ASSET_A);
+    create_asset(ASSET_B);
+    for who in [ALICE, BOB] {
+        mint_asset(ASSET_A, who, 1_000_000);
+        mint_asset(ASSET_B, who, 1_000_000);
+    }
+    assert_ok!(Dex::create_weighted_pool(
+        RuntimeOrigin::signed(ALICE),
+        ASSET_A,
+        ASSET_B,
+        SWAP_FEE,
+        Permill::from_percent(80),
+        Permill::from_percent(20)
+    ));
+    assert_ok!(Dex::mint_liquidity(
+        RuntimeOrigin::signed(ALICE),
+        ASSET_A,
+        ASSET_B,
+        80_000,
+        20_000,
+        0,
+        0,
+        None
+    ));
+}
+
+#[test]
+fn weighted_pool_swaps_by_the_weighted_constant_mean() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_weighted_pool();
+
+        // The first deposit mints 80_000^0.8·20_000^0.2
+        let liquidity_pool = Dex::get_pool(ASSET_A, ASSET_B).unwrap();
+        assert_eq!(liquidity_pool.total_liquidity, 60_628);
+        assert_eq!(Assets::balance(LP_TOKEN, ALICE), 59_628);
+
+        // Both assets are worth the same at these reserves, the lighter side moves faster
+        assert_eq!(Dex::quote_exact_in(ASSET_A, ASSET_B, 1_000), Some(966));
+        assert_eq!(Dex::quote_exact_in(ASSET_B, ASSET_A, 1_000), Some(967));
+
+        assert_ok!(swap(BOB, 1_000));
+        assert_eq!(balance(ASSET_B, BOB), 1_000_966);
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((81_000, 19_034)));
+    });
+}
+
+#[test]
+fn weighted_pool_keeps_the_weights_through_deposits_and_withdrawals() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_weighted_pool();
+
+        // Deposits and withdrawals keep the 80/20 split of the reserves
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            8_000,
+            8_000,
+            0,
+            0,
+            None
+        ));
+        assert_eq!(Assets::balance(LP_TOKEN, BOB), 6_062);
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((88_000, 22_000)));
+
+        assert_ok!(burn(BOB, 6_062));
+        assert_eq!(balance(ASSET_A, BOB), 1_000_000 - 8_000 + 7_999);
+        assert_eq!(balance(ASSET_B, BOB), 1_000_000 - 2_000 + 1_999);
+
+        // Buying an exact amount costs at least what selling it would pay
+        assert_eq!(Dex::quote_exact_out(ASSET_A, ASSET_B, 1_000), Some(1_037));
+        assert!(Dex::quote_exact_in(ASSET_A, ASSET_B, 1_037).unwrap() >= 1_000);
+        assert_ok!(Dex::swap_exact_out(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            1_000,
+            1_037,
+            None
+        ));
+    });
+}
+
+#[test]
+fn weighted_pool_weights_are_validated() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_asset(ASSET_A);
+        create_asset(ASSET_B);
+        for (weight_a, weight_b) in [
+            (Permill::from_percent(50), Permill::from_percent(40)),
+            (Permill::from_percent(60), Permill::from_percent(60)),
+            (Permill::from_parts(995_000), Permill::from_parts(5_000)),
+        ] {
+            assert_noop!(
+                Dex::create_weighted_pool(
+                    RuntimeOrigin::signed(ALICE),
+                    ASSET_A,
+                    ASSET_B,
+                    SWAP_FEE,
+                    weight_a,
+                    weight_b
+                ),
+                Error::<Test>::InvalidWeights
+            );
+        }
+
+        // The weights follow the assets they were given for
+        assert_ok!(Dex::create_weighted_pool(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_B,
+            ASSET_A,
+            SWAP_FEE,
+            Permill::from_percent(20),
+            Permill::from_percent(80)
+        ));
+        assert_eq!(
+            Dex::get_pool(ASSET_A, ASSET_B).unwrap().kind,
+            PoolKind::Weighted {
+                weight_a: Permill::from_percent(80),
+                weight_b: Permill::from_percent(20),
+            }
+        );
+    });
+}
+
+// Creates the A/B/C pool and funds Alice and Bob with all three assets
+fn setup_multi_asset_pool() {
+    for asset in [ASSET_A, ASSET_B, ASSET_C] {
+        create_asset(asset);
+        for who in [ALICE, BOB] {
+            mint_asset(asset, who, 1_000_000);
+        }
+    }
+    assert_ok!(Dex::create_multi_asset_pool(
+        RuntimeOrigin::signed(ALICE),
+        BoundedVec::try_from(vec![ASSET_C, ASSET_A, ASSET_B]).unwrap(),
+        SWAP_FEE
+    ));
+}
+
+fn multi_asset_amounts(
+    amounts: Vec<(AssetKind, Balance)>,
+) -> BoundedVec<(AssetKind, Balance), ConstU32<4>> {
+    BoundedVec::try_from(amounts).unwrap()
+}
+
+fn multi_asset_pool_assets() -> BoundedVec<AssetKind, ConstU32<4>> {
+    BoundedVec::try_from(vec![ASSET_A, ASSET_B, ASSET_C]).unwrap()
+}
+
+fn multi_asset_reserves() -> Vec<Balance> {
+    MultiAssetPools::<Test>::get(multi_asset_pool_assets())
+        .unwrap()
+        .reserves
+        .into_inner()
+}
+
+#[test]
+fn multi_asset_pool_deposits_and_withdraws_every_asset() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_multi_asset_pool();
+        assert_eq!(
+            MultiAssetLiquidityTokens::<Test>::get(LP_TOKEN),
+            Some(multi_asset_pool_assets())
+        );
+
+        // The first deposit mints the geometric mean of the amounts, in any order of the assets
+        assert_ok!(Dex::mint_multi_asset_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            multi_asset_amounts(vec![
+                (ASSET_B, 20_000),
+                (ASSET_C, 40_000),
+                (ASSET_A, 10_000)
+            ]),
+            0,
+            None
+        ));
+        assert_eq!(Assets::balance(LP_TOKEN, ALICE), 18_999);
+        assert_eq!(multi_asset_reserves(), vec![10_000, 20_000, 40_000]);
+
+        // Later deposits keep the ratio of the reserves, taking no more than desired
+        assert_ok!(Dex::mint_multi_asset_liquidity(
+            RuntimeOrigin::signed(BOB),
+            multi_asset_amounts(vec![(ASSET_A, 1_000), (ASSET_B, 5_000), (ASSET_C, 5_000)]),
+            1_999,
+            None
+        ));
+        assert_eq!(Assets::balance(LP_TOKEN, BOB), 1_999);
+        assert_eq!(multi_asset_reserves(), vec![11_000, 22_000, 43_999]);
+        System::assert_last_event(RuntimeEvent::Dex(Event::MultiAssetLiquidityMinted(
+            BOB,
+            multi_asset_pool_assets(),
+            1_999,
+            BoundedVec::try_from(vec![1_000, 2_000, 3_999]).unwrap(),
+        )));
+
+        // Withdrawals pay out a share of every asset, rounded down
+        assert_noop!(
+            Dex::burn_multi_asset_liquidity(
+                RuntimeOrigin::signed(BOB),
+                1_999,
+                multi_asset_amounts(vec![(ASSET_A, 0), (ASSET_B, 0), (ASSET_C, 3_999)]),
+                None
+            ),
+            Error::<Test>::InsufficientAmountsOut
+        );
+        assert_ok!(Dex::burn_multi_asset_liquidity(
+            RuntimeOrigin::signed(BOB),
+            1_999,
+            multi_asset_amounts(vec![(ASSET_A, 0), (ASSET_B, 0), (ASSET_C, 0)]),
+            None
+        ));
+        assert_eq!(balance(ASSET_A, BOB), 1_000_000 - 1);
+        assert_eq!(balance(ASSET_B, BOB), 1_000_000 - 1);
+        assert_eq!(balance(ASSET_C, BOB), 1_000_000 - 1);
+        assert_eq!(multi_asset_reserves(), vec![10_001, 20_001, 40_001]);
+    });
+}
+
+#[test]
+fn multi_asset_pool_swaps_between_any_two_assets() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_multi_asset_pool();
+        assert_ok!(Dex::mint_multi_asset_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            multi_asset_amounts(vec![
+                (ASSET_A, 10_000),
+                (ASSET_B, 20_000),
+                (ASSET_C, 40_000)
+            ]),
+            0,
+            None
+        ));
+
+        // Each swap is priced by the constant product of the two assets traded
+        assert_ok!(Dex::swap_multi_asset(
+            RuntimeOrigin::signed(BOB),
+            multi_asset_pool_assets(),
+            ASSET_A,
+            ASSET_C,
+            1_000,
+            3_626,
+            None
+        ));
+        assert_eq!(balance(ASSET_C, BOB), 1_000_000 + 3_626);
+        assert_eq!(multi_asset_reserves(), vec![11_000, 20_000, 36_374]);
+
+        assert_noop!(
+            Dex::swap_multi_asset(
+                RuntimeOrigin::signed(BOB),
+                multi_asset_pool_assets(),
+                ASSET_C,
+                ASSET_B,
+                1_000,
+                534,
+                None
+            ),
+            Error::<Test>::InsufficientAmountOut
+        );
+        assert_ok!(Dex::swap_multi_asset(
+            RuntimeOrigin::signed(BOB),
+            multi_asset_pool_assets(),
+            ASSET_C,
+            ASSET_B,
+            1_000,
+            533,
+            None
+        ));
+        assert_eq!(balance(ASSET_B, BOB), 1_000_000 + 533);
+        assert_eq!(multi_asset_reserves(), vec![11_000, 19_467, 37_374]);
+        System::assert_last_event(RuntimeEvent::Dex(Event::Swapped(
+            BOB, ASSET_C, 1_000, ASSET_B, 533,
+        )));
+
+        // Only members of the pool can be traded
+        assert_noop!(
+            Dex::swap_multi_asset(
+                RuntimeOrigin::signed(BOB),
+                multi_asset_pool_assets(),
+                NATIVE,
+                ASSET_B,
+                1_000,
+                0,
+                None
+            ),
+            Error::<Test>::InvalidAssetIn
+        );
+        assert_noop!(
+            Dex::swap_multi_asset(
+                RuntimeOrigin::signed(BOB),
+                multi_asset_pool_assets(),
+                ASSET_B,
+                ASSET_B,
+                1_000,
+                0,
+                None
+            ),
+            Error::<Test>::InvalidAssetOut
+        );
+    });
+}
+
+#[test]
+fn multi_asset_pool_assets_are_validated() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_multi_asset_pool();
+
+        for assets in [vec![ASSET_A], vec![ASSET_A, ASSET_B, ASSET_A]] {
+            assert_noop!(
+                Dex::create_multi_asset_pool(
+                    RuntimeOrigin::signed(ALICE),
+                    BoundedVec::try_from(assets).unwrap(),
+                    SWAP_FEE
+                ),
+                Error::<Test>::InvalidPoolAssets
+            );
+        }
+
+        // The pool is keyed by its sorted assets
+        assert_noop!(
+            Dex::create_multi_asset_pool(
+                RuntimeOrigin::signed(ALICE),
+                BoundedVec::try_from(vec![ASSET_B, ASSET_C, ASSET_A]).unwrap(),
+                SWAP_FEE
+            ),
+            Error::<Test>::LiquidityPoolAlreadyExists
+        );
+
+        // Deposits must cover every asset of an existing pool
+        assert_noop!(
+            Dex::mint_multi_asset_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                multi_asset_amounts(vec![(ASSET_A, 10_000), (ASSET_B, 10_000)]),
+                0,
+                None
+            ),
+            Error::<Test>::LiquidityPoolNotFound
+        );
+
+        // A pool of a subset of the assets is a different pool
+        assert_ok!(Dex::create_multi_asset_pool(
+            RuntimeOrigin::signed(ALICE),
+            BoundedVec::try_from(vec![ASSET_A, ASSET_B]).unwrap(),
+            SWAP_FEE
+        ));
+        assert_eq!(
+            MultiAssetLiquidityTokens::<Test>::get(LP_TOKEN + 1),
+            Some(BoundedVec::try_from(vec![ASSET_A, ASSET_B]).unwrap())
+        );
+    });
+}
+
+// Deposits 10_000 A, 20_000 B and 40_000 C into the A/B/C pool from Alice
+fn mint_multi_asset_liquidity() {
+    assert_ok!(Dex::mint_multi_asset_liquidity(
+        RuntimeOrigin::signed(ALICE),
+        multi_asset_amounts(vec![
+            (ASSET_A, 10_000),
+            (ASSET_B, 20_000),
+            (ASSET_C, 40_000)
+        ]),
+        0,
+        None
+    ));
+}
+
+#[test]
+fn multi_asset_pool_status_is_checked() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_multi_asset_pool();
+        mint_multi_asset_liquidity();
+
+        assert_noop!(
+            Dex::set_multi_asset_pool_status(
+                RuntimeOrigin::signed(ALICE),
+                multi_asset_pool_assets(),
+                PoolStatus::FullyPaused
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Dex::set_multi_asset_pool_status(
+            RuntimeOrigin::root(),
+            multi_asset_pool_assets(),
+            PoolStatus::FullyPaused
+        ));
+        System::assert_last_event(
+            Event::MultiAssetPoolStatusUpdated(multi_asset_pool_assets(), PoolStatus::FullyPaused)
+                .into(),
+        );
+
+        assert_noop!(
+            Dex::mint_multi_asset_liquidity(
+                RuntimeOrigin::signed(BOB),
+                multi_asset_amounts(vec![(ASSET_A, 1_000), (ASSET_B, 2_000), (ASSET_C, 4_000)]),
+                0,
+                None
+            ),
+            Error::<Test>::PoolPaused
+        );
+        assert_noop!(
+            Dex::burn_multi_asset_liquidity(
+                RuntimeOrigin::signed(ALICE),
+                1_000,
+                multi_asset_amounts(vec![(ASSET_A, 0), (ASSET_B, 0), (ASSET_C, 0)]),
+                None
+            ),
+            Error::<Test>::PoolPaused
+        );
+        assert_noop!(
+            Dex::swap_multi_asset(
+                RuntimeOrigin::signed(BOB),
+                multi_asset_pool_assets(),
+                ASSET_A,
+                ASSET_C,
+                1_000,
+                0,
+                None
+            ),
+            Error::<Test>::PoolPaused
+        );
+
+        assert_ok!(Dex::set_multi_asset_pool_status(
+            RuntimeOrigin::root(),
+            multi_asset_pool_assets(),
+            PoolStatus::Active
+        ));
+        assert_ok!(Dex::swap_multi_asset(
+            RuntimeOrigin::signed(BOB),
+            multi_asset_pool_assets(),
+            ASSET_A,
+            ASSET_C,
+            1_000,
+            0,
+            None
+        ));
+    });
+}
+
+#[test]
+fn multi_asset_pool_accrues_protocol_fees() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_multi_asset_pool();
+        mint_multi_asset_liquidity();
+
+        assert_noop!(
+            Dex::set_multi_asset_protocol_fee(
+                RuntimeOrigin::signed(ALICE),
+                multi_asset_pool_assets(),
+                Permill::from_percent(50)
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Dex::set_multi_asset_protocol_fee(
+                RuntimeOrigin::root(),
+                multi_asset_pool_assets(),
+                Permill::from_percent(60)
+            ),
+            Error::<Test>::InvalidProtocolFee
+        );
+        assert_ok!(Dex::set_multi_asset_protocol_fee(
+            RuntimeOrigin::root(),
+            multi_asset_pool_assets(),
+            Permill::from_percent(50)
+        ));
+
+        // Half of the 30 A swap fee is withheld from the reserves, the swap pays out as before
+        assert_ok!(Dex::swap_multi_asset(
+            RuntimeOrigin::signed(BOB),
+            multi_asset_pool_assets(),
+            ASSET_A,
+            ASSET_C,
+            10_000,
+            19_969,
+            None
+        ));
+        assert_eq!(multi_asset_reserves(), vec![19_985, 20_000, 20_031]);
+        System::assert_has_event(
+            Event::MultiAssetProtocolFeeAccrued(multi_asset_pool_assets(), ASSET_A, 15).into(),
+        );
+        let multi_asset_pool = MultiAssetPools::<Test>::get(multi_asset_pool_assets()).unwrap();
+        assert_eq!(multi_asset_pool.protocol_fees.into_inner(), vec![15, 0, 0]);
+
+        assert_ok!(Dex::claim_multi_asset_protocol_fees(
+            RuntimeOrigin::signed(BOB),
+            multi_asset_pool_assets()
+        ));
+        System::assert_last_event(Event::ProtocolFeeCollected(TREASURY, ASSET_A, 15).into());
+        assert_eq!(balance(ASSET_A, TREASURY), 15);
+        let multi_asset_pool = MultiAssetPools::<Test>::get(multi_asset_pool_assets()).unwrap();
+        assert_eq!(multi_asset_pool.protocol_fees.into_inner(), vec![0, 0, 0]);
+        assert_eq!(multi_asset_reserves(), vec![19_985, 20_000, 20_031]);
+    });
+}
+
+#[test]
+fn destroy_multi_asset_pool_releases_the_deposit() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_multi_asset_pool();
+        let hold_reason = RuntimeHoldReason::Dex(HoldReason::PoolCreation);
+        assert!(Balances::balance_on_hold(&hold_reason, &ALICE) > 0);
+
+        // Only the depositor can destroy a pool that never held liquidity
+        assert_noop!(
+            Dex::destroy_multi_asset_pool(RuntimeOrigin::signed(BOB), multi_asset_pool_assets()),
+            Error::<Test>::NotPoolDepositor
+        );
+
+        // Liquidity outside of the pool account has to be burned first
+        mint_multi_asset_liquidity();
+        assert_noop!(
+            Dex::destroy_multi_asset_pool(RuntimeOrigin::signed(BOB), multi_asset_pool_assets()),
+            Error::<Test>::LiquidityPoolNotEmpty
+        );
+        assert_ok!(Dex::burn_multi_asset_liquidity(
+            RuntimeOrigin::signed(ALICE),
+            18_999,
+            multi_asset_amounts(vec![(ASSET_A, 0), (ASSET_B, 0), (ASSET_C, 0)]),
+            None
+        ));
+
+        // The share of the locked liquidity goes to the protocol fee receiver
+        assert_ok!(Dex::destroy_multi_asset_pool(
+            RuntimeOrigin::signed(BOB),
+            multi_asset_pool_assets()
+        ));
+        System::assert_last_event(
+            Event::MultiAssetPoolDestroyed(multi_asset_pool_assets(), LP_TOKEN).into(),
+        );
+        System::assert_has_event(
+            Event::MultiAssetPoolSwept(
+                multi_asset_pool_assets(),
+                TREASURY,
+                BoundedVec::try_from(vec![501, 1_001, 2_002]).unwrap(),
+            )
+            .into(),
+        );
+        assert_eq!(balance(ASSET_A, TREASURY), 501);
+        assert_eq!(balance(ASSET_B, TREASURY), 1_001);
+        assert_eq!(balance(ASSET_C, TREASURY), 2_002);
+        assert!(!MultiAssetPools::<Test>::contains_key(
+            multi_asset_pool_assets()
+        ));
+        assert_eq!(MultiAssetLiquidityTokens::<Test>::get(LP_TOKEN), None);
+        assert_eq!(Balances::balance_on_hold(&hold_reason, &ALICE), 0);
+        assert_eq!(Balances::free_balance(ALICE), 1_000_000);
+    });
+}
+
+// Creates the A/B concentrated liquidity pool at tick zero and funds Alice and Bob with both
+// assets
+fn setup_concentrated_pool() {
+    create_asset(ASSET_A);
+    create_asset(ASSET_B);
+    for who in [ALICE, BOB] {
+        mint_asset(ASSET_A, who, 1_000_000);
+        mint_asset(ASSET_B, who, 1_000_000);
+    }
+    assert_ok!(Dex::create_concentrated_pool(
+        RuntimeOrigin::signed(ALICE),
+        ASSET_A,
+        ASSET_B,
+        SWAP_FEE,
+        10,
+        0
+    ));
+}
+
+fn open_position(who: u64, tick_lower: i32, tick_upper: i32, liquidity: u128) -> DispatchResult {
+    Dex::open_position(
+        RuntimeOrigin::signed(who),
+        ASSET_A,
+        ASSET_B,
+        tick_lower,
+        tick_upper,
+        liquidity,
+        Balance::MAX,
+        Balance::MAX,
+        None,
+    )
+}
+
+fn collect_position(who: u64, tick_lower: i32, tick_upper: i32) -> DispatchResult {
+    Dex::collect_position(
+        RuntimeOrigin::signed(who),
+        ASSET_A,
+        ASSET_B,
+        tick_lower,
+        tick_upper,
+        None,
+    )
+}
+
+fn concentrated_state() -> ConcentratedLiquidity {
+    Dex::get_pool(ASSET_A, ASSET_B)
+        .unwrap()
+        .concentrated()
+        .unwrap()
+}
+
+fn initialized_ticks() -> Vec<i32> {
+    Ticks::<Test>::get(AssetPair::new(ASSET_A, ASSET_B))
+        .keys()
+        .copied()
+        .collect()
+}
+
+#[test]
+fn concentrated_positions_deposit_the_assets_of_their_range() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_concentrated_pool();
+
+        // A range around the price takes both assets
+        assert_ok!(open_position(ALICE, -1_000, 1_000, 1_000_000));
+        assert_eq!(balance(ASSET_A, ALICE), 1_000_000 - 48_769);
+        assert_eq!(balance(ASSET_B, ALICE), 1_000_000 - 48_769);
+        System::assert_last_event(RuntimeEvent::Dex(Event::PositionIncreased(
+            ALICE, ASSET_A, ASSET_B, -1_000, 1_000, 1_000_000, 48_769, 48_769,
+        )));
+
+        // A range above the price only takes asset A, one below it only asset B
+        assert_ok!(open_position(BOB, 100, 1_000, 1_000_000));
+        assert_ok!(open_position(BOB, -1_000, -100, 1_000_000));
+        assert_eq!(balance(ASSET_A, BOB), 1_000_000 - 43_781);
+        assert_eq!(balance(ASSET_B, BOB), 1_000_000 - 43_781);
+
+        // Only the liquidity in range is active
+        assert_eq!(concentrated_state().liquidity, 1_000_000);
+        assert_eq!(initialized_ticks(), vec![-1_000, -100, 100, 1_000]);
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((92_550, 92_550)));
+
+        assert_noop!(
+            open_position(ALICE, -1_000, 1_000, 1_000_000),
+            Error::<Test>::PositionAlreadyExists
+        );
+        assert_noop!(
+            Dex::open_position(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                -1_000,
+                1_000,
+                1_000_000,
+                48_768,
+                48_769,
+                None
+            ),
+            Error::<Test>::ExcessiveAmountIn
+        );
+        for (tick_lower, tick_upper) in [(5, 100), (100, -100), (MIN_TICK - 4, 0)] {
+            assert_noop!(
+                open_position(BOB, tick_lower, tick_upper, 1_000_000),
+                Error::<Test>::InvalidTickRange
+            );
+        }
+
+        // Positions hold no liquidity tokens and keep the pool from being destroyed
+        assert_noop!(
+            Dex::mint_liquidity(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                10_000,
+                10_000,
+                0,
+                0,
+                None
+            ),
+            Error::<Test>::ConcentratedLiquidityPool
+        );
+        assert_noop!(
+            Dex::destroy_pool(RuntimeOrigin::signed(BOB), ASSET_A, ASSET_B),
+            Error::<Test>::LiquidityPoolNotEmpty
+        );
+    });
+}
+
+#[test]
+fn swaps_are_only_charged_for_the_ticks_they_cross() {
+    new_test_ext().execute_with(|| {
+        setup_pool();
+        assert_ok!(mint(ALICE, 10_000));
+        let post_info = swap(BOB, 1_000).unwrap();
+        assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::swap()));
+    });
+
+    new_test_ext().execute_with(|| {
+        setup_concentrated_pool();
+        assert_ok!(open_position(ALICE, -1_000, 1_000, 1_000_000));
+        assert_ok!(open_position(BOB, -100, 100, 1_000_000));
+
+        // Staying within Bob's range crosses no tick, leaving it crosses its lower one
+        let post_info = swap(BOB, 1_000).unwrap();
+        assert_eq!(
+            post_info.actual_weight,
+            Some(<() as WeightInfo>::swap_concentrated(0))
+        );
+        let post_info = swap(BOB, 20_000).unwrap();
+        assert_eq!(
+            post_info.actual_weight,
+            Some(<() as WeightInfo>::swap_concentrated(1))
+        );
+    });
+}
+
+#[test]
+fn concentrated_swaps_cross_initialized_ticks() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_concentrated_pool();
+        assert_ok!(open_position(ALICE, -1_000, 1_000, 1_000_000));
+        assert_ok!(open_position(BOB, -100, 100, 1_000_000));
+        assert_eq!(concentrated_state().liquidity, 2_000_000);
+
+        // Selling asset A moves the price down out of Bob's range
+        assert_eq!(Dex::quote_exact_in(ASSET_A, ASSET_B, 20_000), Some(19_693));
+        assert_ok!(swap(BOB, 20_000));
+        assert_eq!(balance(ASSET_B, BOB), 1_000_000 - 4_988 + 19_693);
+        let state = concentrated_state();
+        assert_eq!((state.tick, state.liquidity), (-297, 1_000_000));
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((73_757, 34_064)));
+
+        // Selling asset B moves it back up into the range
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_B,
+            ASSET_A,
+            20_000,
+            20_181,
+            None
+        ));
+        let state = concentrated_state();
+        assert_eq!((state.tick, state.liquidity), (2, 2_000_000));
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((53_576, 54_064)));
+
+        // Nothing is left to swap against past the last position
+        assert_noop!(
+            Dex::swap(
+                RuntimeOrigin::signed(BOB),
+                ASSET_B,
+                ASSET_A,
+                200_000,
+                0,
+                None
+            ),
+            Error::<Test>::InsufficientLiquidity
+        );
+    });
+}
+
+#[test]
+fn concentrated_swaps_for_an_exact_amount_out() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_concentrated_pool();
+        assert_ok!(open_position(ALICE, -1_000, 1_000, 1_000_000));
+        assert_ok!(open_position(BOB, -100, 100, 1_000_000));
+
+        // Buying asset B costs what selling asset A for the same amount pays
+        assert_eq!(Dex::quote_exact_out(ASSET_A, ASSET_B, 19_693), Some(20_000));
+        assert_noop!(
+            Dex::swap_exact_out(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                19_693,
+                19_999,
+                None
+            ),
+            Error::<Test>::ExcessiveAmountIn
+        );
+        assert_ok!(Dex::swap_exact_out(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            19_693,
+            20_000,
+            None
+        ));
+        System::assert_last_event(RuntimeEvent::Dex(Event::SwappedExactOut(
+            BOB, ASSET_A, 20_000, ASSET_B, 19_693,
+        )));
+        assert_eq!(balance(ASSET_A, BOB), 1_000_000 - 4_988 - 20_000);
+        assert_eq!(balance(ASSET_B, BOB), 1_000_000 - 4_988 + 19_693);
+        let state = concentrated_state();
+        assert_eq!((state.tick, state.liquidity), (-297, 1_000_000));
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((73_757, 34_064)));
+
+        // Buying asset A crosses back into Bob's range
+        assert_ok!(Dex::swap_exact_out(
+            RuntimeOrigin::signed(BOB),
+            ASSET_B,
+            ASSET_A,
+            20_181,
+            20_000,
+            None
+        ));
+        let state = concentrated_state();
+        assert_eq!((state.tick, state.liquidity), (2, 2_000_000));
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((53_576, 54_064)));
+
+        // More than the positions hold cannot be bought
+        assert_eq!(Dex::quote_exact_out(ASSET_B, ASSET_A, 53_575), None);
+        assert_noop!(
+            Dex::swap_exact_out(
+                RuntimeOrigin::signed(BOB),
+                ASSET_B,
+                ASSET_A,
+                53_575,
+                Balance::MAX,
+                None
+            ),
+            Error::<Test>::InsufficientLiquidity
+        );
+    });
+}
+
+#[test]
+fn concentrated_exact_out_swaps_finish_next_to_a_tick() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_concentrated_pool();
+        assert_ok!(open_position(ALICE, -1_000, 1_000, 1_000_000));
+        assert_ok!(open_position(BOB, -100, 100, 1_000_000));
+
+        // A single unit of asset A is left before the end of Bob's range
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_B,
+            ASSET_A,
+            10_054,
+            0,
+            None
+        ));
+        let state = concentrated_state();
+        assert_eq!((state.tick, state.liquidity), (99, 2_000_000));
+
+        // Tiny amounts out are paid in full, across the tick and without stalling
+        assert_eq!(Dex::quote_exact_out(ASSET_B, ASSET_A, 1), Some(3));
+        assert_eq!(Dex::quote_exact_out(ASSET_B, ASSET_A, 3), Some(7));
+        assert_ok!(Dex::swap_exact_out(
+            RuntimeOrigin::signed(BOB),
+            ASSET_B,
+            ASSET_A,
+            3,
+            7,
+            None
+        ));
+        System::assert_last_event(RuntimeEvent::Dex(Event::SwappedExactOut(
+            BOB, ASSET_B, 7, ASSET_A, 3,
+        )));
+        let state = concentrated_state();
+        assert_eq!((state.tick, state.liquidity), (100, 1_000_000));
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((43_781, 63_818)));
+    });
+}
+
+#[test]
+fn concentrated_positions_earn_the_fees_of_their_range() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_concentrated_pool();
+        assert_ok!(open_position(ALICE, -1_000, 1_000, 1_000_000));
+        assert_ok!(open_position(BOB, -100, 100, 1_000_000));
+        assert_ok!(open_position(ALICE, 200, 1_000, 1_000_000));
+
+        // Both swaps stay within Bob's range
+        assert_ok!(swap(BOB, 5_000));
+        assert_ok!(Dex::swap(
+            RuntimeOrigin::signed(BOB),
+            ASSET_B,
+            ASSET_A,
+            5_000,
+            4_997,
+            None
+        ));
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((92_579, 53_785)));
+
+        // Removed liquidity is owed to the position with its fees until collected
+        assert_noop!(
+            Dex::decrease_position(
+                RuntimeOrigin::signed(BOB),
+                ASSET_A,
+                ASSET_B,
+                -100,
+                100,
+                1_000_000,
+                4_982,
+                0,
+                None
+            ),
+            Error::<Test>::InsufficientAmountsOut
+        );
+        assert_ok!(Dex::decrease_position(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            -100,
+            100,
+            1_000_000,
+            4_981,
+            4_993,
+            None
+        ));
+        let bob_a = balance(ASSET_A, BOB);
+        let bob_b = balance(ASSET_B, BOB);
+        assert_ok!(collect_position(BOB, -100, 100));
+        assert_eq!(balance(ASSET_A, BOB), bob_a + 4_988);
+        assert_eq!(balance(ASSET_B, BOB), bob_b + 5_000);
+        System::assert_last_event(RuntimeEvent::Dex(Event::PositionCollected(
+            BOB, BOB, ASSET_A, ASSET_B, -100, 100, 4_988, 5_000,
+        )));
+        let trading_pair = AssetPair::new(ASSET_A, ASSET_B);
+        assert!(!Positions::<Test>::contains_key(
+            &trading_pair,
+            (BOB, -100, 100)
+        ));
+        assert_eq!(initialized_ticks(), vec![-1_000, 200, 1_000]);
+
+        // Alice shared the fees while in range, her position out of range earned nothing
+        let alice_a = balance(ASSET_A, ALICE);
+        let alice_b = balance(ASSET_B, ALICE);
+        assert_ok!(collect_position(ALICE, -1_000, 1_000));
+        assert_ok!(collect_position(ALICE, 200, 1_000));
+        assert_eq!(balance(ASSET_A, ALICE), alice_a + 7);
+        assert_eq!(balance(ASSET_B, ALICE), alice_b + 7);
+        let position = Positions::<Test>::get(&trading_pair, (ALICE, -1_000, 1_000)).unwrap();
+        assert_eq!(
+            (position.liquidity, position.tokens_owed),
+            (1_000_000, (0, 0))
+        );
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((87_584, 48_778)));
+
+        assert_noop!(
+            collect_position(BOB, -100, 100),
+            Error::<Test>::PositionNotFound
+        );
+    });
+}
+
+// Creates the A/B pool priced by the constant sum curve of the mock runtime, holding 50_000 of
+// both assets deposited by Alice, and funds Alice and Bob with both assets
+fn setup_constant_sum_pool() {
+    create_asset(ASSET_A);
+    create_asset(ASSET_B);
+    for who in [ALICE, BOB] {
+        mint_asset(ASSET_A, who, 1_000_000);
+        mint_asset(ASSET_B, who, 1_000_000);
+    }
+    assert_ok!(Dex::create_curve_pool(
+        RuntimeOrigin::signed(ALICE),
+        ASSET_A,
+        ASSET_B,
+        SWAP_FEE,
+        TestCurve::ConstantSum
+    ));
+    assert_ok!(Dex::mint_liquidity(
+        RuntimeOrigin::signed(ALICE),
+        ASSET_A,
+        ASSET_B,
+        50_000,
+        50_000,
+        0,
+        0,
+        None
+    ));
+}
+
+#[test]
+fn create_curve_pool_records_the_curve() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_pool();
+        create_asset(ASSET_C);
+
+        // Plain pools use the runtime's default curve
+        assert_eq!(
+            Dex::get_pool(ASSET_A, ASSET_B).unwrap().kind,
+            PoolKind::Curve(TestCurve::ConstantProduct)
+        );
+
+        assert_ok!(Dex::create_curve_pool(
+            RuntimeOrigin::signed(ALICE),
+            ASSET_B,
+            ASSET_C,
+            SWAP_FEE,
+            TestCurve::ConstantSum
+        ));
+        assert_eq!(
+            Dex::get_pool(ASSET_B, ASSET_C).unwrap().kind,
+            PoolKind::Curve(TestCurve::ConstantSum)
+        );
+
+        assert_noop!(
+            Dex::create_curve_pool(
+                RuntimeOrigin::signed(ALICE),
+                ASSET_A,
+                ASSET_B,
+                SWAP_FEE,
+                TestCurve::ConstantSum
+            ),
+            Error::<Test>::LiquidityPoolAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn curve_pool_swaps_by_its_curve() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_constant_sum_pool();
+
+        // The first deposit mints the sum of the amounts
+        let liquidity_pool = Dex::get_pool(ASSET_A, ASSET_B).unwrap();
+        assert_eq!(liquidity_pool.total_liquidity, 100_000);
+        assert_eq!(Assets::balance(LP_TOKEN, ALICE), 99_000);
+
+        // The assets swap one to one, less the swap fee
+        assert_eq!(Dex::quote_exact_in(ASSET_A, ASSET_B, 10_000), Some(9_970));
+        assert_eq!(Dex::quote_exact_out(ASSET_A, ASSET_B, 1_000), Some(1_004));
+        assert_ok!(swap(BOB, 10_000));
+        assert_eq!(balance(ASSET_B, BOB), 1_009_970);
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((60_000, 40_030)));
+
+        // The curve refuses to pay out the whole reserve
+        assert_eq!(Dex::quote_exact_in(ASSET_A, ASSET_B, 41_000), None);
+    });
+}
+
+#[test]
+fn curve_pool_mints_and_burns_by_its_curve() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_constant_sum_pool();
+        assert_ok!(swap(BOB, 10_000));
+
+        // Later deposits mint in proportion to the reserves they add
+        assert_ok!(Dex::mint_liquidity(
+            RuntimeOrigin::signed(BOB),
+            ASSET_A,
+            ASSET_B,
+            6_000,
+            6_000,
+            0,
+            0,
+            None
+        ));
+        assert_eq!(Assets::balance(LP_TOKEN, BOB), 10_000);
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((66_000, 44_033)));
+
+        assert_ok!(burn(BOB, 10_000));
+        assert_eq!(balance(ASSET_A, BOB), 1_000_000 - 10_000);
+        assert_eq!(balance(ASSET_B, BOB), 1_009_970);
+        assert_eq!(Dex::get_reserves(ASSET_A, ASSET_B), Some((60_000, 40_030)));
+    });
+}
+
+#[test]
+fn constant_product_handles_reserves_above_u64() {
+    let (amount, reserve) = (1u128 << 90, 1u128 << 100);
+    let curve = ConstantProduct;
+
+    assert_eq!(
+        curve.amount_out(amount, reserve, reserve),
+        Ok(1_236_732_292_905_589_659_996_783_615)
+    );
+    assert_eq!(
+        curve.amount_in(amount, reserve, reserve),
+        Ok(1_239_150_146_850_664_126_585_242_626)
+    );
+    assert_eq!(
+        curve.mint_shares((reserve, reserve), (0, 0), 0),
+        Ok(reserve)
+    );
+    assert_eq!(
+        curve.mint_shares((amount, amount), (reserve, reserve), reserve),
+        Ok(amount)
+    );
+    assert_eq!(
+        curve.burn_amounts(reserve >> 1, (reserve, reserve << 1), reserve),
+        Ok((reserve >> 1, reserve))
+    );
+    assert_eq!(
+        curve.amount_out(u128::MAX, u128::MAX, u128::MAX),
+        Ok(u128::MAX / 2)
+    );
+}
+
+// Property-based checks of the pool invariants over random sequences of calls
+mod invariants {
+    use super::*;
+    use proptest::prelude::*;
+
+    const USERS: [u64; 3] = [ALICE, BOB, CHARLIE];
+    const PAIRS: [(AssetKind, AssetKind); 3] =
+        [(ASSET_A, ASSET_B), (ASSET_B, ASSET_C), (NATIVE, ASSET_A)];
+
+    #[derive(Clone, Debug)]
+    enum Action {
+        Create {
+            pair: usize,
+            fee: u32,
+        },
+        Mint {
+            who: u64,
+            pair: usize,
+            amounts: (Balance, Balance),
+        },
+        Burn {
+            who: u64,
+            pair: usize,
+            percent: u8,
+        },
+        Swap {
+            who: u64,
+            pair: usize,
+            a_to_b: bool,
+            amount: Balance,
+        },
+        // Mint and immediately burn the liquidity received
+        MintBurn {
+            who: u64,
+            pair: usize,
+            amounts: (Balance, Balance),
+        },
+        // Swap and immediately swap the amount received back
+        SwapBack {
+            who: u64,
+            pair: usize,
+            a_to_b: bool,
+            amount: Balance,
+        },
+    }
+
+    fn action() -> impl Strategy<Value = Action> {
+        let who = || prop::sample::select(USERS.to_vec());
+        let pair = || 0..PAIRS.len();
+        let amount = || 1..100_000 as Balance;
+        prop_oneof![
+            (pair(), 1..=100u32).prop_map(|(pair, fee)| Action::Create { pair, fee }),
+            (who(), pair(), amount(), amount()).prop_map(|(who, pair, a, b)| Action::Mint {
+                who,
+                pair,
+                amounts: (a, b)
+            }),
+            (who(), pair(), 1..=100u8).prop_map(|(who, pair, percent)| Action::Burn {
+                who,
+                pair,
+                percent
+            }),
+            (who(), pair(), any::<bool>(), amount()).prop_map(|(who, pair, a_to_b, amount)| {
+                Action::Swap {
+                    who,
+                    pair,
+                    a_to_b,
+                    amount,
+                }
+            }),
+            (who(), pair(), amount(), amount()).prop_map(|(who, pair, a, b)| {
+                Action::MintBurn {
+                    who,
+                    pair,
+                    amounts: (a, b),
+                }
+            }),
+            (who(), pair(), any::<bool>(), amount()).prop_map(|(who, pair, a_to_b, amount)| {
+                Action::SwapBack {
+                    who,
+                    pair,
+                    a_to_b,
+                    amount,
+                }
+            }),
+        ]
+    }
+
+    fn sorted(pair: usize) -> AssetPair<Test> {
+        AssetPair::new(PAIRS[pair].0, PAIRS[pair].1)
+    }
+
+    fn reserves(pair: usize) -> Option<(Balance, Balance)> {
+        Dex::get_reserves(PAIRS[pair].0, PAIRS[pair].1)
+    }
+
+    fn liquidity_of(who: u64, pair: usize) -> Balance {
+        Dex::lp_token_for_pair(PAIRS[pair].0, PAIRS[pair].1)
+            .map(|liquidity_token| Assets::balance(liquidity_token, who))
+            .unwrap_or_default()
+    }
+
+    fn mint(who: u64, pair: usize, amounts: (Balance, Balance)) -> DispatchResult {
+        let trading_pair = sorted(pair);
+        Dex::mint_liquidity(
+            RuntimeOrigin::signed(who),
+            trading_pair.asset_a,
+            trading_pair.asset_b,
+            amounts.0,
+            amounts.1,
+            0,
+            0,
+            None,
+        )
+    }
+
+    fn burn(who: u64, pair: usize, liquidity: Balance) -> DispatchResult {
+        let trading_pair = sorted(pair);
+        Dex::burn_liquidity(
+            RuntimeOrigin::signed(who),
+            trading_pair.asset_a,
+            trading_pair.asset_b,
+            liquidity,
+            0,
+            0,
+            None,
+            None,
+        )
+    }
+
+    fn swap(who: u64, pair: usize, a_to_b: bool, amount: Balance) -> DispatchResultWithPostInfo {
+        let (asset_a, asset_b) = PAIRS[pair];
+        let (asset_in, asset_out) = if a_to_b {
+            (asset_a, asset_b)
+        } else {
+            (asset_b, asset_a)
+        };
+        Dex::swap(
+            RuntimeOrigin::signed(who),
+            asset_in,
+            asset_out,
+            amount,
+            0,
+            None,
+        )
+    }
+
+    fn apply(action: Action) {
+        match action {
+            Action::Create { pair, fee } => {
+                let _ = Dex::create_liquidity_pool(
+                    RuntimeOrigin::signed(ALICE),
+                    PAIRS[pair].0,
+                    PAIRS[pair].1,
+                    Permill::from_perthousand(fee),
+                );
+            }
+            Action::Mint { who, pair, amounts } => {
+                let _ = mint(who, pair, amounts);
+            }
+            Action::Burn { who, pair, percent } => {
+                let liquidity = liquidity_of(who, pair) * percent as Balance / 100;
+                let _ = burn(who, pair, liquidity);
+            }
+            Action::Swap {
+                who,
+                pair,
+                a_to_b,
+                amount,
+            } => {
+                let before = reserves(pair);
+                if swap(who, pair, a_to_b, amount).is_ok() {
+                    // The product of the reserves never decreases on swaps
+                    let (before, after) = (before.unwrap(), reserves(pair).unwrap());
+                    assert!(after.0 * after.1 >= before.0 * before.1);
+                }
+            }
+            Action::MintBurn { who, pair, amounts } => {
+                let (asset_a, asset_b) = PAIRS[pair];
+                let balances = (balance(asset_a, who), balance(asset_b, who));
+                let liquidity = liquidity_of(who, pair);
+                if mint(who, pair, amounts).is_ok() {
+                    let minted = liquidity_of(who, pair) - liquidity;
+                    assert_ok!(burn(who, pair, minted));
+                    assert!(balance(asset_a, who) <= balances.0);
+                    assert!(balance(asset_b, who) <= balances.1);
+                }
+            }
+            Action::SwapBack {
+                who,
+                pair,
+                a_to_b,
+                amount,
+            } => {
+                let (asset_a, asset_b) = PAIRS[pair];
+                let (asset_in, asset_out) = if a_to_b {
+                    (asset_a, asset_b)
+                } else {
+                    (asset_b, asset_a)
+                };
+                let balances = (balance(asset_in, who), balance(asset_out, who));
+                if swap(who, pair, a_to_b, amount).is_ok() {
+                    let received = balance(asset_out, who) - balances.1;
+                    if swap(who, pair, !a_to_b, received).is_ok() {
+                        assert!(balance(asset_in, who) <= balances.0);
+                        assert!(balance(asset_out, who) <= balances.1);
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_invariants() {
+        for (asset_a, asset_b) in PAIRS {
+            let Some(pool) = Dex::get_pool(asset_a, asset_b) else {
+                continue;
+            };
+
+            // The supply of the liquidity token is the pool's total liquidity
+            assert_eq!(
+                Assets::total_issuance(pool.liquidity_token),
+                pool.total_liquidity
+            );
+
+            // The pool account holds at least the reserves
+            let drift = Dex::reserve_drift(asset_a, asset_b).unwrap();
+            assert_eq!(drift.shortfall, (0, 0));
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn invariants_hold_for_random_calls(actions in prop::collection::vec(action(), 1..40)) {
+            new_test_ext().execute_with(|| {
+                for asset in [ASSET_A, ASSET_B, ASSET_C] {
+                    create_asset(asset);
+                    for who in USERS {
+                        mint_asset(asset, who, 10_000_000);
+                    }
+                }
+
+                for action in actions {
+                    apply(action);
+                    check_invariants();
+                }
+            });
+        }
+    }
+}
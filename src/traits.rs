@@ -0,0 +1,197 @@
+use frame_support::pallet_prelude::*;
+use sp_core::U256;
+use sp_runtime::traits::{AtLeast32BitUnsigned, SaturatedConversion};
+use sp_runtime::{ArithmeticError, FixedPointNumber, FixedU128};
+use sp_std::marker::PhantomData;
+
+/// Allocates the asset ids used for the liquidity tokens of newly created pools.
+pub trait AssetIdGenerator<AssetId> {
+    /// The id handed out to the very first pool.
+    fn initial_id() -> AssetId;
+
+    /// The id following `previous`, or `None` once the id space is exhausted.
+    fn next_id(previous: &AssetId) -> Option<AssetId>;
+}
+
+/// Hands out consecutive asset ids starting at `Start`.
+///
+/// Runtimes should pick a `Start` that leaves enough room below it for regularly created
+/// assets. Pool creation skips ids that are already taken, but gives up until it is retried once
+/// more than `MAX_LIQUIDITY_TOKEN_ID_ATTEMPTS` in a row are.
+pub struct SequentialAssetIds<Start>(PhantomData<Start>);
+
+impl<Start: Get<u32>> AssetIdGenerator<u32> for SequentialAssetIds<Start> {
+    fn initial_id() -> u32 {
+        Start::get()
+    }
+
+    fn next_id(previous: &u32) -> Option<u32> {
+        previous.checked_add(1)
+    }
+}
+
+/// An invariant pricing the swaps, deposits and withdrawals of a pool of two assets.
+///
+/// Swap fees are left to the pool: `amount_out` is given the amount in after the fee is taken,
+/// and `amount_in` returns the amount the pool must keep after the fee. Shares are the liquidity
+/// tokens of the pool.
+pub trait AmmCurve<Balance: AtLeast32BitUnsigned + Copy> {
+    /// The amount out for swapping `amount_in` against `reserve_in` and `reserve_out`, rounded
+    /// down so that rounding favours the pool.
+    fn amount_out(
+        &self,
+        amount_in: Balance,
+        reserve_in: Balance,
+        reserve_out: Balance,
+    ) -> Result<Balance, DispatchError>;
+
+    /// The amount in required to swap for exactly `amount_out`, rounded up so that rounding
+    /// favours the pool.
+    fn amount_in(
+        &self,
+        amount_out: Balance,
+        reserve_in: Balance,
+        reserve_out: Balance,
+    ) -> Result<Balance, DispatchError>;
+
+    /// The shares minted for depositing `amounts` into a pool holding `reserves` with
+    /// `total_shares` issued. The first deposit finds both at zero.
+    fn mint_shares(
+        &self,
+        amounts: (Balance, Balance),
+        reserves: (Balance, Balance),
+        total_shares: Balance,
+    ) -> Result<Balance, DispatchError>;
+
+    /// The amounts withdrawn for burning `shares` of a pool holding `reserves` with
+    /// `total_shares` issued.
+    fn burn_amounts(
+        &self,
+        shares: Balance,
+        reserves: (Balance, Balance),
+        total_shares: Balance,
+    ) -> Result<(Balance, Balance), DispatchError>;
+
+    /// The marginal price of the asset in, in the asset out, feeding the price oracle. Defaults
+    /// to the ratio of the reserves.
+    fn spot_price(&self, reserve_in: Balance, reserve_out: Balance) -> FixedU128 {
+        FixedU128::saturating_from_rational(
+            reserve_out.saturated_into::<u128>(),
+            reserve_in.saturated_into::<u128>(),
+        )
+    }
+}
+
+/// The constant product curve, keeping `reserve_a·reserve_b` constant through swaps.
+#[derive(
+    Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub struct ConstantProduct;
+
+impl<Balance: AtLeast32BitUnsigned + Copy> AmmCurve<Balance> for ConstantProduct {
+    fn amount_out(
+        &self,
+        amount_in: Balance,
+        reserve_in: Balance,
+        reserve_out: Balance,
+    ) -> Result<Balance, DispatchError> {
+        // amount_in·reserve_out / (reserve_in + amount_in)
+        let amount_in = to_u256(amount_in);
+        let numerator = amount_in
+            .checked_mul(to_u256(reserve_out))
+            .ok_or(ArithmeticError::Overflow)?;
+        let denominator = to_u256(reserve_in)
+            .checked_add(amount_in)
+            .ok_or(ArithmeticError::Overflow)?;
+        let amount_out = numerator
+            .checked_div(denominator)
+            .ok_or(ArithmeticError::DivisionByZero)?;
+        from_u256(amount_out)
+    }
+
+    fn amount_in(
+        &self,
+        amount_out: Balance,
+        reserve_in: Balance,
+        reserve_out: Balance,
+    ) -> Result<Balance, DispatchError> {
+        // reserve_in·amount_out / (reserve_out - amount_out), rounded up
+        let numerator = to_u256(reserve_in)
+            .checked_mul(to_u256(amount_out))
+            .ok_or(ArithmeticError::Overflow)?;
+        let denominator = to_u256(reserve_out)
+            .checked_sub(to_u256(amount_out))
+            .ok_or(ArithmeticError::Underflow)?;
+        from_u256(div_ceil(numerator, denominator)?)
+    }
+
+    fn mint_shares(
+        &self,
+        amounts: (Balance, Balance),
+        reserves: (Balance, Balance),
+        total_shares: Balance,
+    ) -> Result<Balance, DispatchError> {
+        // The first deposit mints the geometric mean of the amounts
+        if total_shares.is_zero() {
+            let product = to_u256(amounts.0)
+                .checked_mul(to_u256(amounts.1))
+                .ok_or(ArithmeticError::Overflow)?;
+            return from_u256(product.integer_sqrt());
+        }
+
+        // Later deposits mint in proportion to the smaller share of the reserves they add
+        let shares_a = proportion(amounts.0, total_shares, reserves.0)?;
+        let shares_b = proportion(amounts.1, total_shares, reserves.1)?;
+        Ok(shares_a.min(shares_b))
+    }
+
+    fn burn_amounts(
+        &self,
+        shares: Balance,
+        reserves: (Balance, Balance),
+        total_shares: Balance,
+    ) -> Result<(Balance, Balance), DispatchError> {
+        Ok((
+            proportion(shares, reserves.0, total_shares)?,
+            proportion(shares, reserves.1, total_shares)?,
+        ))
+    }
+}
+
+// Helper function to widen a balance, the products of balances are computed in 256 bits
+fn to_u256<Balance: AtLeast32BitUnsigned + Copy>(balance: Balance) -> U256 {
+    U256::from(balance.saturated_into::<u128>())
+}
+
+// Helper function to narrow a result back to a balance
+fn from_u256<Balance: AtLeast32BitUnsigned + Copy>(value: U256) -> Result<Balance, DispatchError> {
+    let value = u128::try_from(value).map_err(|_| ArithmeticError::Overflow)?;
+    Balance::try_from(value).map_err(|_| ArithmeticError::Overflow.into())
+}
+
+// Helper function to compute amount·numerator / denominator, rounded down
+fn proportion<Balance: AtLeast32BitUnsigned + Copy>(
+    amount: Balance,
+    numerator: Balance,
+    denominator: Balance,
+) -> Result<Balance, DispatchError> {
+    let proportion = to_u256(amount)
+        .checked_mul(to_u256(numerator))
+        .ok_or(ArithmeticError::Overflow)?
+        .checked_div(to_u256(denominator))
+        .ok_or(ArithmeticError::DivisionByZero)?;
+    from_u256(proportion)
+}
+
+// Helper function for integer division rounding up
+fn div_ceil(numerator: U256, denominator: U256) -> Result<U256, DispatchError> {
+    ensure!(!denominator.is_zero(), ArithmeticError::DivisionByZero);
+    let quotient = numerator / denominator;
+    if (numerator % denominator).is_zero() {
+        return Ok(quotient);
+    }
+    let rounded_up = quotient
+        .checked_add(U256::one())
+        .ok_or(ArithmeticError::Overflow)?;
+    Ok(rounded_up)
+}
@@ -0,0 +1,120 @@
+// Fixed-point logarithm, exponential and power for the swap math of weighted pools, computed on
+// the inner values of `FixedU128` and `FixedI128` so that the results are the same natively and
+// in Wasm. The series stop once their terms no longer change the 18th decimal, which bounds the
+// relative error of `pow` well below `MAX_POW_RELATIVE_ERROR`.
+
+use sp_runtime::helpers_128bit::multiply_by_rational_with_rounding;
+use sp_runtime::traits::{CheckedAdd, CheckedMul, One, Saturating, Zero};
+use sp_runtime::{FixedI128, FixedPointNumber, FixedU128, Rounding};
+
+/// The relative error `pow` is rounded by, as the inner value of a `FixedU128`
+pub const MAX_POW_RELATIVE_ERROR: u128 = 10_000;
+
+// ln(2) as the inner value of a fixed point number
+const LN_2: u128 = 693_147_180_559_945_309;
+
+// Function to compute the natural logarithm of `x`, `None` for zero
+pub fn ln(x: FixedU128) -> Option<FixedI128> {
+    let one = FixedU128::DIV;
+    let mut mantissa = x.into_inner();
+    if mantissa == 0 {
+        return None;
+    }
+
+    // x = mantissa·2^k with 1 <= mantissa < 2
+    let mut k: i128 = 0;
+    while mantissa >= 2 * one {
+        mantissa /= 2;
+        k += 1;
+    }
+    while mantissa < one {
+        mantissa *= 2;
+        k -= 1;
+    }
+
+    // ln(mantissa) = 2·atanh(z) = 2·(z + z³/3 + z⁵/5 + …) with z = (mantissa - 1) / (mantissa + 1),
+    // which is below 1/3 so the series converges quickly
+    let z = (mantissa - one) * one / (mantissa + one);
+    let z_squared = z * z / one;
+    let mut term = z;
+    let mut sum: u128 = 0;
+    let mut n: u128 = 1;
+    while term > 0 {
+        sum += term / n;
+        term = term * z_squared / one;
+        n += 2;
+    }
+
+    let ln_mantissa = i128::try_from(2 * sum).ok()?;
+    let ln_two_k = k.checked_mul(LN_2 as i128)?;
+    Some(FixedI128::from_inner(ln_two_k.checked_add(ln_mantissa)?))
+}
+
+// Function to compute e^x, `None` if the result does not fit a `FixedU128`
+pub fn exp(x: FixedI128) -> Option<FixedU128> {
+    let one = FixedU128::DIV;
+
+    // x = k·ln(2) + r with 0 <= r < ln(2)
+    let k = x.into_inner().div_euclid(LN_2 as i128);
+    let remainder = x.into_inner().rem_euclid(LN_2 as i128) as u128;
+
+    // e^r = 1 + r + r²/2! + r³/3! + …
+    let mut term = one;
+    let mut sum: u128 = 0;
+    let mut n: u128 = 1;
+    while term > 0 {
+        sum += term;
+        term = term * remainder / one / n;
+        n += 1;
+    }
+
+    // e^x = e^r·2^k
+    let result = if k >= 0 {
+        let shift = u32::try_from(k).ok()?;
+        if shift >= sum.leading_zeros() {
+            return None;
+        }
+        sum << shift
+    } else {
+        let shift = u32::try_from(-k).ok()?;
+        sum.checked_shr(shift).unwrap_or(0)
+    };
+    Some(FixedU128::from_inner(result))
+}
+
+// Function to compute base^exponent as e^(exponent·ln(base)), `None` if it overflows
+pub fn pow(base: FixedU128, exponent: FixedU128) -> Option<FixedU128> {
+    if exponent.is_zero() {
+        return Some(FixedU128::one());
+    }
+    if base.is_zero() {
+        return Some(FixedU128::zero());
+    }
+
+    let exponent = FixedI128::from_inner(i128::try_from(exponent.into_inner()).ok()?);
+    exp(ln(base)?.checked_mul(&exponent)?)
+}
+
+// Function to compute base^exponent rounded up by the largest error of `pow`
+pub fn pow_up(base: FixedU128, exponent: FixedU128) -> Option<FixedU128> {
+    let power = pow(base, exponent)?;
+    power.checked_add(&max_error(power))
+}
+
+// Function to compute base^exponent rounded down by the largest error of `pow`
+pub fn pow_down(base: FixedU128, exponent: FixedU128) -> Option<FixedU128> {
+    let power = pow(base, exponent)?;
+    Some(power.saturating_sub(max_error(power)))
+}
+
+// Helper function to get the largest error of a result of `pow`, at least one unit
+fn max_error(power: FixedU128) -> FixedU128 {
+    let error = multiply_by_rational_with_rounding(
+        power.into_inner(),
+        MAX_POW_RELATIVE_ERROR,
+        FixedU128::DIV,
+        Rounding::Up,
+    )
+    .unwrap_or(u128::MAX);
+    FixedU128::from_inner(error.saturating_add(1))
+}
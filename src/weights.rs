@@ -0,0 +1,840 @@
+
+//! Weights for pallet_dex
+//!
+//! These are placeholder values estimated by hand, not benchmark results. They have to be
+//! replaced with the output of `benchmark pallet` for `pallet_dex` on the reference hardware,
+//! using the benchmarks in `benchmarking.rs`, before the pallet goes to production.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_dex.
+pub trait WeightInfo {
+	fn create_liquidity_pool() -> Weight;
+	fn mint_liquidity() -> Weight;
+	fn burn_liquidity() -> Weight;
+	fn swap() -> Weight;
+	fn swap_exact_out() -> Weight;
+	fn swap_exact_in_path(n: u32, ) -> Weight;
+	fn swap_exact_out_path(n: u32, ) -> Weight;
+	fn set_swap_fee() -> Weight;
+	fn set_protocol_fee() -> Weight;
+	fn sync() -> Weight;
+	fn skim() -> Weight;
+	fn destroy_pool() -> Weight;
+	fn set_asset_listing() -> Weight;
+	fn set_allowlist_enforced() -> Weight;
+	fn set_global_status() -> Weight;
+	fn set_pool_status() -> Weight;
+	fn create_stable_pool() -> Weight;
+	fn ramp_amplification() -> Weight;
+	fn stop_amplification_ramp() -> Weight;
+	fn create_weighted_pool() -> Weight;
+	fn create_multi_asset_pool(n: u32, ) -> Weight;
+	fn mint_multi_asset_liquidity(n: u32, ) -> Weight;
+	fn burn_multi_asset_liquidity(n: u32, ) -> Weight;
+	fn swap_multi_asset() -> Weight;
+	fn create_concentrated_pool() -> Weight;
+	fn open_position() -> Weight;
+	fn increase_position() -> Weight;
+	fn decrease_position() -> Weight;
+	fn collect_position() -> Weight;
+	fn create_curve_pool() -> Weight;
+	fn claim_protocol_fees() -> Weight;
+	fn set_multi_asset_pool_status() -> Weight;
+	fn set_multi_asset_protocol_fee() -> Weight;
+	fn claim_multi_asset_protocol_fees(n: u32, ) -> Weight;
+	fn destroy_multi_asset_pool(n: u32, ) -> Weight;
+	fn swap_concentrated(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_dex using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex NextLiquidityTokenId (r:1 w:1)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Dex LiquidityTokens (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:0 w:1)
+	fn create_liquidity_pool() -> Weight {
+		Weight::from_parts(70_000_000, 9_110)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:3 w:3)
+	/// Storage: Assets Account (r:6 w:6)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Dex PriceObservations (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	fn mint_liquidity() -> Weight {
+		Weight::from_parts(160_000_000, 16_602)
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:3 w:3)
+	/// Storage: Assets Account (r:5 w:5)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex PriceObservations (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	fn burn_liquidity() -> Weight {
+		Weight::from_parts(145_000_000, 14_034)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:5 w:5)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	fn swap() -> Weight {
+		Weight::from_parts(130_000_000, 15_106)
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:5 w:5)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	fn swap_exact_out() -> Weight {
+		Weight::from_parts(132_000_000, 15_106)
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:3 w:3)
+	/// Storage: Assets Asset (r:4 w:4)
+	/// Storage: Assets Account (r:11 w:11)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:4 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:3 w:3)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// The range of component `n` is `[2, 4]`.
+	fn swap_exact_in_path(n: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 5_000)
+			.saturating_add(Weight::from_parts(95_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 4_087).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Dex LiquidityPools (r:3 w:3)
+	/// Storage: Assets Asset (r:4 w:4)
+	/// Storage: Assets Account (r:11 w:11)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:4 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:3 w:3)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// The range of component `n` is `[2, 4]`.
+	fn swap_exact_out_path(n: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 5_000)
+			.saturating_add(Weight::from_parts(97_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 4_087).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	fn set_swap_fee() -> Weight {
+		Weight::from_parts(18_000_000, 3_601)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	fn set_protocol_fee() -> Weight {
+		Weight::from_parts(18_000_000, 3_601)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:0)
+	/// Storage: Assets Account (r:2 w:0)
+	/// Storage: Dex PriceObservations (r:1 w:1)
+	fn sync() -> Weight {
+		Weight::from_parts(35_000_000, 7_198)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:0)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:4 w:4)
+	/// Storage: System Account (r:1 w:1)
+	fn skim() -> Weight {
+		Weight::from_parts(110_000_000, 10_732)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:3 w:3)
+	/// Storage: Assets Account (r:5 w:5)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Dex LiquidityTokens (r:0 w:1)
+	/// Storage: Dex PriceObservations (r:0 w:1)
+	fn destroy_pool() -> Weight {
+		Weight::from_parts(150_000_000, 16_602)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+	}
+	/// Storage: Dex AssetListings (r:0 w:1)
+	fn set_asset_listing() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex AllowlistEnforced (r:0 w:1)
+	fn set_allowlist_enforced() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex GlobalStatus (r:0 w:1)
+	fn set_global_status() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	fn set_pool_status() -> Weight {
+		Weight::from_parts(18_000_000, 3_601)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex NextLiquidityTokenId (r:1 w:1)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Dex LiquidityTokens (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:0 w:1)
+	fn create_stable_pool() -> Weight {
+		Weight::from_parts(72_000_000, 9_110)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	fn ramp_amplification() -> Weight {
+		Weight::from_parts(19_000_000, 3_601)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	fn stop_amplification_ramp() -> Weight {
+		Weight::from_parts(18_000_000, 3_601)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex NextLiquidityTokenId (r:1 w:1)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Dex LiquidityTokens (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:0 w:1)
+	fn create_weighted_pool() -> Weight {
+		Weight::from_parts(72_000_000, 9_110)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	/// Storage: Dex NextLiquidityTokenId (r:1 w:1)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Dex LiquidityTokens (r:1 w:0)
+	/// Storage: Dex MultiAssetLiquidityTokens (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Dex AssetListings (r:4 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// The range of component `n` is `[2, 4]`.
+	fn create_multi_asset_pool(n: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 9_500)
+			.saturating_add(Weight::from_parts(3_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2_499).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Assets Asset (r:5 w:5)
+	/// Storage: Assets Account (r:10 w:10)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `n` is `[2, 4]`.
+	fn mint_multi_asset_liquidity(n: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 6_000)
+			.saturating_add(Weight::from_parts(45_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 5_180).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Assets Asset (r:5 w:5)
+	/// Storage: Assets Account (r:9 w:9)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `n` is `[2, 4]`.
+	fn burn_multi_asset_liquidity(n: u32, ) -> Weight {
+		Weight::from_parts(55_000_000, 6_000)
+			.saturating_add(Weight::from_parts(42_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 5_180).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:4 w:4)
+	/// Storage: System Account (r:1 w:1)
+	fn swap_multi_asset() -> Weight {
+		Weight::from_parts(110_000_000, 12_500)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex NextLiquidityTokenId (r:1 w:1)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Dex LiquidityTokens (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:0 w:1)
+	fn create_concentrated_pool() -> Weight {
+		Weight::from_parts(73_000_000, 9_110)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dex Positions (r:1 w:1)
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Dex Ticks (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:4 w:4)
+	fn open_position() -> Weight {
+		Weight::from_parts(95_000_000, 14_500)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: Dex Positions (r:1 w:1)
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Dex Ticks (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:4 w:4)
+	fn increase_position() -> Weight {
+		Weight::from_parts(93_000_000, 14_500)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: Dex Positions (r:1 w:1)
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Dex Ticks (r:1 w:1)
+	fn decrease_position() -> Weight {
+		Weight::from_parts(42_000_000, 8_200)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Dex Positions (r:1 w:1)
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Dex Ticks (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:4 w:4)
+	fn collect_position() -> Weight {
+		Weight::from_parts(88_000_000, 14_500)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex NextLiquidityTokenId (r:1 w:1)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Dex LiquidityTokens (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:0 w:1)
+	fn create_curve_pool() -> Weight {
+		Weight::from_parts(70_000_000, 9_110)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:4 w:4)
+	fn claim_protocol_fees() -> Weight {
+		Weight::from_parts(62_000_000, 10_410)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	fn set_multi_asset_pool_status() -> Weight {
+		Weight::from_parts(19_000_000, 3_861)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	fn set_multi_asset_protocol_fee() -> Weight {
+		Weight::from_parts(19_000_000, 3_861)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	/// Storage: Assets Asset (r:4 w:4)
+	/// Storage: Assets Account (r:8 w:8)
+	/// The range of component `n` is `[2, 4]`.
+	fn claim_multi_asset_protocol_fees(n: u32, ) -> Weight {
+		Weight::from_parts(24_000_000, 3_861)
+			.saturating_add(Weight::from_parts(31_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 5_180).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	/// Storage: Assets Asset (r:5 w:5)
+	/// Storage: Assets Account (r:9 w:9)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Dex MultiAssetLiquidityTokens (r:0 w:1)
+	/// The range of component `n` is `[2, 4]`.
+	fn destroy_multi_asset_pool(n: u32, ) -> Weight {
+		Weight::from_parts(95_000_000, 8_600)
+			.saturating_add(Weight::from_parts(33_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 5_180).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:5 w:5)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Dex Ticks (r:1 w:1)
+	/// The range of component `n` is `[0, 62]`.
+	fn swap_concentrated(n: u32, ) -> Weight {
+		Weight::from_parts(130_000_000, 15_106)
+			.saturating_add(Weight::from_parts(20_625_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 150).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(15_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex NextLiquidityTokenId (r:1 w:1)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Dex LiquidityTokens (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:0 w:1)
+	fn create_liquidity_pool() -> Weight {
+		Weight::from_parts(70_000_000, 9_110)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:3 w:3)
+	/// Storage: Assets Account (r:6 w:6)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Dex PriceObservations (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	fn mint_liquidity() -> Weight {
+		Weight::from_parts(160_000_000, 16_602)
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:3 w:3)
+	/// Storage: Assets Account (r:5 w:5)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex PriceObservations (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	fn burn_liquidity() -> Weight {
+		Weight::from_parts(145_000_000, 14_034)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:5 w:5)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	fn swap() -> Weight {
+		Weight::from_parts(130_000_000, 15_106)
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:5 w:5)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	fn swap_exact_out() -> Weight {
+		Weight::from_parts(132_000_000, 15_106)
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:3 w:3)
+	/// Storage: Assets Asset (r:4 w:4)
+	/// Storage: Assets Account (r:11 w:11)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:4 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:3 w:3)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// The range of component `n` is `[2, 4]`.
+	fn swap_exact_in_path(n: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 5_000)
+			.saturating_add(Weight::from_parts(95_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 4_087).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Dex LiquidityPools (r:3 w:3)
+	/// Storage: Assets Asset (r:4 w:4)
+	/// Storage: Assets Account (r:11 w:11)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:4 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:3 w:3)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// The range of component `n` is `[2, 4]`.
+	fn swap_exact_out_path(n: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 5_000)
+			.saturating_add(Weight::from_parts(97_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 4_087).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	fn set_swap_fee() -> Weight {
+		Weight::from_parts(18_000_000, 3_601)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	fn set_protocol_fee() -> Weight {
+		Weight::from_parts(18_000_000, 3_601)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:0)
+	/// Storage: Assets Account (r:2 w:0)
+	/// Storage: Dex PriceObservations (r:1 w:1)
+	fn sync() -> Weight {
+		Weight::from_parts(35_000_000, 7_198)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:0)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:4 w:4)
+	/// Storage: System Account (r:1 w:1)
+	fn skim() -> Weight {
+		Weight::from_parts(110_000_000, 10_732)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:3 w:3)
+	/// Storage: Assets Account (r:5 w:5)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Dex LiquidityTokens (r:0 w:1)
+	/// Storage: Dex PriceObservations (r:0 w:1)
+	fn destroy_pool() -> Weight {
+		Weight::from_parts(150_000_000, 16_602)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+	}
+	/// Storage: Dex AssetListings (r:0 w:1)
+	fn set_asset_listing() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex AllowlistEnforced (r:0 w:1)
+	fn set_allowlist_enforced() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex GlobalStatus (r:0 w:1)
+	fn set_global_status() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	fn set_pool_status() -> Weight {
+		Weight::from_parts(18_000_000, 3_601)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex NextLiquidityTokenId (r:1 w:1)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Dex LiquidityTokens (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:0 w:1)
+	fn create_stable_pool() -> Weight {
+		Weight::from_parts(72_000_000, 9_110)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	fn ramp_amplification() -> Weight {
+		Weight::from_parts(19_000_000, 3_601)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	fn stop_amplification_ramp() -> Weight {
+		Weight::from_parts(18_000_000, 3_601)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex NextLiquidityTokenId (r:1 w:1)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Dex LiquidityTokens (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:0 w:1)
+	fn create_weighted_pool() -> Weight {
+		Weight::from_parts(72_000_000, 9_110)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	/// Storage: Dex NextLiquidityTokenId (r:1 w:1)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Dex LiquidityTokens (r:1 w:0)
+	/// Storage: Dex MultiAssetLiquidityTokens (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Dex AssetListings (r:4 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// The range of component `n` is `[2, 4]`.
+	fn create_multi_asset_pool(n: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 9_500)
+			.saturating_add(Weight::from_parts(3_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 2_499).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Assets Asset (r:5 w:5)
+	/// Storage: Assets Account (r:10 w:10)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `n` is `[2, 4]`.
+	fn mint_multi_asset_liquidity(n: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 6_000)
+			.saturating_add(Weight::from_parts(45_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 5_180).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Assets Asset (r:5 w:5)
+	/// Storage: Assets Account (r:9 w:9)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `n` is `[2, 4]`.
+	fn burn_multi_asset_liquidity(n: u32, ) -> Weight {
+		Weight::from_parts(55_000_000, 6_000)
+			.saturating_add(Weight::from_parts(42_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 5_180).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:4 w:4)
+	/// Storage: System Account (r:1 w:1)
+	fn swap_multi_asset() -> Weight {
+		Weight::from_parts(110_000_000, 12_500)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex NextLiquidityTokenId (r:1 w:1)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Dex LiquidityTokens (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:0 w:1)
+	fn create_concentrated_pool() -> Weight {
+		Weight::from_parts(73_000_000, 9_110)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dex Positions (r:1 w:1)
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Dex Ticks (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:4 w:4)
+	fn open_position() -> Weight {
+		Weight::from_parts(95_000_000, 14_500)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: Dex Positions (r:1 w:1)
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Dex Ticks (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:4 w:4)
+	fn increase_position() -> Weight {
+		Weight::from_parts(93_000_000, 14_500)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: Dex Positions (r:1 w:1)
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Dex Ticks (r:1 w:1)
+	fn decrease_position() -> Weight {
+		Weight::from_parts(42_000_000, 8_200)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Dex Positions (r:1 w:1)
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Dex Ticks (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:4 w:4)
+	fn collect_position() -> Weight {
+		Weight::from_parts(88_000_000, 14_500)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Dex NextLiquidityTokenId (r:1 w:1)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Dex LiquidityTokens (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:0 w:1)
+	fn create_curve_pool() -> Weight {
+		Weight::from_parts(70_000_000, 9_110)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:4 w:4)
+	fn claim_protocol_fees() -> Weight {
+		Weight::from_parts(62_000_000, 10_410)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	fn set_multi_asset_pool_status() -> Weight {
+		Weight::from_parts(19_000_000, 3_861)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	fn set_multi_asset_protocol_fee() -> Weight {
+		Weight::from_parts(19_000_000, 3_861)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	/// Storage: Assets Asset (r:4 w:4)
+	/// Storage: Assets Account (r:8 w:8)
+	/// The range of component `n` is `[2, 4]`.
+	fn claim_multi_asset_protocol_fees(n: u32, ) -> Weight {
+		Weight::from_parts(24_000_000, 3_861)
+			.saturating_add(Weight::from_parts(31_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 5_180).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Dex MultiAssetPools (r:1 w:1)
+	/// Storage: Assets Asset (r:5 w:5)
+	/// Storage: Assets Account (r:9 w:9)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: Dex MultiAssetLiquidityTokens (r:0 w:1)
+	/// The range of component `n` is `[2, 4]`.
+	fn destroy_multi_asset_pool(n: u32, ) -> Weight {
+		Weight::from_parts(95_000_000, 8_600)
+			.saturating_add(Weight::from_parts(33_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 5_180).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	/// Storage: Dex LiquidityPools (r:1 w:1)
+	/// Storage: Assets Asset (r:2 w:2)
+	/// Storage: Assets Account (r:5 w:5)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dex AssetListings (r:2 w:0)
+	/// Storage: Dex AllowlistEnforced (r:1 w:0)
+	/// Storage: Dex PriceObservations (r:1 w:1)
+	/// Storage: Dex GlobalStatus (r:1 w:0)
+	/// Storage: Dex Ticks (r:1 w:1)
+	/// The range of component `n` is `[0, 62]`.
+	fn swap_concentrated(n: u32, ) -> Weight {
+		Weight::from_parts(130_000_000, 15_106)
+			.saturating_add(Weight::from_parts(20_625_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(0, 150).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(15_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+}